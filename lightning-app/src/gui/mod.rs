@@ -8,6 +8,8 @@ use crate::config::Config;
 use crate::tree_gl::hover::QuadTreeHover;
 use egui_glow::egui_winit::winit::event::Modifiers;
 use lightning_model::build::Build;
+use lightning_model::build::evaluator::StatTrace;
+use lightning_model::diagnostics::Diagnostics;
 use lightning_model::data::tree::Node;
 use lightning_model::data::GEMS;
 use lightning_model::gem::Gem;
@@ -60,8 +62,15 @@ pub struct State {
     pub modifiers: Modifiers,
 
     pub active_skill_calc: FxHashMap<&'static str, i64>,
+    pub offence_calc: calc::BuildOffence,
     pub defence_calc: FxHashMap<&'static str, i64>,
     pub defence_stats: lightning_model::build::stat::Stats,
+    pub trace_stats: bool,
+    pub defence_traces: FxHashMap<lightning_model::build::stat::StatId, StatTrace>,
+    /// Gems and items left out when importing the build
+    pub load_diagnostics: Diagnostics,
+    /// Everything making the calculations incomplete, refreshed on recalc
+    pub diagnostics: Diagnostics,
     pub delta_compare: FxHashMap<&'static str, i64>,
     pub delta_compare_single: FxHashMap<&'static str, i64>,
     pub power_report: Option<PowerReport>,
@@ -122,8 +131,13 @@ impl State {
             modifiers: Default::default(),
 
             active_skill_calc: FxHashMap::default(),
+            offence_calc: Default::default(),
             defence_calc: FxHashMap::default(),
             defence_stats: Default::default(),
+            trace_stats: false,
+            defence_traces: FxHashMap::default(),
+            load_diagnostics: Diagnostics::default(),
+            diagnostics: Diagnostics::default(),
             delta_compare: FxHashMap::default(),
             delta_compare_single: FxHashMap::default(),
             power_report: None,
@@ -215,14 +229,16 @@ impl State {
 
     pub fn compare(&self, build_compare: &Build) -> FxHashMap<&'static str, i64> {
         let mut delta = FxHashMap::default();
+        let mut leeching = false;
         if let Some(gem_link_compare) = build_compare.gem_links.get(self.gemlink_cur) {
             if let Some(active_gem_compare) = gem_link_compare.active_gems().nth(self.active_skill_cur) {
                 let supports: Vec<&Gem> = gem_link_compare.support_gems().filter(|g| g.enabled).map(|arc_gem| arc_gem.as_ref()).collect();
                 let active_gem_compare_calc = calc::calc_gem(build_compare, &supports, active_gem_compare);
+                leeching = calc::is_leeching(&active_gem_compare_calc);
                 delta.extend(calc::compare(&self.active_skill_calc, &active_gem_compare_calc));
             }
         }
-        let (defence_compare_calc, _) = calc::calc_defence(build_compare);
+        let (defence_compare_calc, _) = calc::calc_defence(build_compare, leeching);
         delta.extend(calc::compare(&self.defence_calc, &defence_compare_calc));
         delta
     }
@@ -234,7 +250,20 @@ impl State {
         self.passives_count = self.build.tree.passives_count();
         self.passives_max = stats.val(lightning_model::build::stat::StatId::PassiveSkillPoints);
         self.abyssal_sockets = stats.val(lightning_model::build::stat::StatId::AbyssalSockets) as u16;
-        let (defence_calc, mut defence_stats) = calc::calc_defence(&self.build);
+        self.active_skill_calc.clear();
+        if let Some(gem_link) = self.build.gem_links.get(self.gemlink_cur) {
+            if let Some(active_gem) = gem_link.active_gems().nth(self.active_skill_cur) {
+                let supports: Vec<&Gem> = gem_link.support_gems().filter(|g| g.enabled).map(|arc_gem| arc_gem.as_ref()).collect();
+                self.active_skill_calc = calc::calc_gem(&self.build, &supports, active_gem);
+            }
+        }
+        // The defences depend on whether the active skill leeches
+        let leeching = calc::is_leeching(&self.active_skill_calc);
+        let (defence_calc, mut defence_stats) = calc::calc_defence(&self.build, leeching);
+        self.diagnostics = self.load_diagnostics.clone();
+        self.diagnostics.extend(self.build.diagnostics());
+        self.diagnostics.extend(stats.diagnostics);
+        self.diagnostics.extend(defence_stats.diagnostics.clone());
         for stat in defence_stats.stats.values_mut() {
             stat.mods.sort_unstable_by(|a, b| {
                 let type_score = |t: lightning_model::modifier::Type| match t {
@@ -254,13 +283,13 @@ impl State {
         }
         self.defence_calc = defence_calc;
         self.defence_stats = defence_stats;
-        self.active_skill_calc.clear();
-        if let Some(gem_link) = self.build.gem_links.get(self.gemlink_cur) {
-            if let Some(active_gem) = gem_link.active_gems().nth(self.active_skill_cur) {
-                let supports: Vec<&Gem> = gem_link.support_gems().filter(|g| g.enabled).map(|arc_gem| arc_gem.as_ref()).collect();
-                self.active_skill_calc = calc::calc_gem(&self.build, &supports, active_gem);
+        self.defence_traces.clear();
+        if self.trace_stats {
+            for stat_id in panel::calc::TRACED_STATS {
+                self.defence_traces.insert(stat_id, calc::trace_defence_stat(&self.build, stat_id, leeching));
             }
         }
+        self.offence_calc = calc::calc_build_offence(&self.build);
         if let Some(build_compare) = self.build_compare.as_ref() {
             self.delta_compare = self.compare(build_compare);
         }
@@ -296,7 +325,7 @@ impl State {
         if self.panel_bottom.power_report_checkbox {
             let string = self.panel_bottom.power_report_selected.0;
             self.power_report = match self.panel_bottom.power_report_selected.1 {
-                PowerReportType::Defence => Some(PowerReport::new_defence(&self.build, string)),
+                PowerReportType::Defence => Some(PowerReport::new_defence(&self.build, string, calc::is_leeching(&self.active_skill_calc))),
                 PowerReportType::Gem => {
                     if let Some(gem_link) = self.build.gem_links.get(self.gemlink_cur) {
                         if let Some(active_gem) = gem_link.active_gems().nth(self.active_skill_cur) {
@@ -31,7 +31,12 @@ const POWER_REPORT_OPTIONS: &[(&'static str, PowerReportType)] = &[
     ("Crit Chance (MH)", PowerReportType::Gem),
     ("Crit Chance (OH)", PowerReportType::Gem),
     ("Crit Multi", PowerReportType::Gem),
+    ("DoT DPS", PowerReportType::Gem),
     ("Bleed DPS", PowerReportType::Gem),
+    ("Ignite DPS", PowerReportType::Gem),
+    ("Poison DPS", PowerReportType::Gem),
+    ("Impale DPS", PowerReportType::Gem),
+    ("Total Minion DPS", PowerReportType::Gem),
     ("Maximum Life", PowerReportType::Defence),
     ("Maximum Mana", PowerReportType::Defence),
     ("Fire Resistance", PowerReportType::Defence),
@@ -40,15 +45,29 @@ const POWER_REPORT_OPTIONS: &[(&'static str, PowerReportType)] = &[
     ("Chaos Resistance", PowerReportType::Defence),
     ("Life Regeneration", PowerReportType::Defence),
     ("Mana Regeneration", PowerReportType::Defence),
+    ("ES Recharge", PowerReportType::Defence),
     ("Strength", PowerReportType::Defence),
     ("Dexterity", PowerReportType::Defence),
     ("Intelligence", PowerReportType::Defence),
     ("Armour", PowerReportType::Defence),
+    ("Physical Damage Reduction", PowerReportType::Defence),
     ("Evasion", PowerReportType::Defence),
+    ("Chance to Evade", PowerReportType::Defence),
+    ("Chance to Avoid Attacks", PowerReportType::Defence),
     ("Energy Shield", PowerReportType::Defence),
     ("Spell Suppression", PowerReportType::Defence),
     ("Block", PowerReportType::Defence),
     ("Spell Block", PowerReportType::Defence),
+    ("Max Physical Hit", PowerReportType::Defence),
+    ("Max Fire Hit", PowerReportType::Defence),
+    ("Max Cold Hit", PowerReportType::Defence),
+    ("Max Lightning Hit", PowerReportType::Defence),
+    ("Max Chaos Hit", PowerReportType::Defence),
+    ("Max Physical Spell Hit", PowerReportType::Defence),
+    ("Max Fire Spell Hit", PowerReportType::Defence),
+    ("Max Cold Spell Hit", PowerReportType::Defence),
+    ("Max Lightning Spell Hit", PowerReportType::Defence),
+    ("Max Chaos Spell Hit", PowerReportType::Defence),
 ];
 
 pub fn draw(ctx: &egui::Context, state: &mut State) {
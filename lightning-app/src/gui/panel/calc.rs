@@ -1,4 +1,5 @@
 use crate::gui::State;
+use lightning_model::build::evaluator::{ModTrace, StatTrace, TraceInput};
 use lightning_model::data::base_item::Rarity;
 use lightning_model::{build::stat::StatId, modifier::Mutation};
 use lightning_model::modifier::Source;
@@ -6,10 +7,24 @@ use lightning_model::modifier::Source;
 use egui::Color32;
 use egui_extras::{Column, TableBuilder};
 
+/// Stats traced when tracing is enabled, the ones with a breakdown below
+pub const TRACED_STATS: [StatId; 5] = [
+    StatId::MaximumLife,
+    StatId::MaximumMana,
+    StatId::Armour,
+    StatId::EvasionRating,
+    StatId::MaximumEnergyShield,
+];
+
 pub fn draw(ctx: &egui::Context, state: &mut State) {
     egui::CentralPanel::default().show(ctx, |ui| {
         egui::ScrollArea::vertical().show(ui, |ui| {
-            ui.heading(egui::RichText::new("Defence Calculations").size(24.0).color(Color32::WHITE));
+            ui.horizontal(|ui| {
+                ui.heading(egui::RichText::new("Defence Calculations").size(24.0).color(Color32::WHITE));
+                if ui.checkbox(&mut state.trace_stats, "Trace").on_hover_text("Show how each stat is derived, slows down recalculation").changed() {
+                    state.request_recalc = true;
+                }
+            });
             egui_flex::Flex::horizontal()
                 .wrap(true)
                 .align_items(egui_flex::FlexAlign::Start)
@@ -95,30 +110,7 @@ fn draw_stat_breakdown(ui: &mut egui::Ui, state: &State, stat_id: StatId) {
                             ui.add(egui::Label::new(format!("{:?}", m.typ)).wrap_mode(egui::TextWrapMode::Extend));
                         });
                         row.col(|ui| {
-                            let source_text = match m.source {
-                                Source::Innate => egui::RichText::new("Innate"),
-                                Source::Node(id) => {
-                                    let name = state.build.tree.nodes_data.get(&id).map(|n| n.name.clone()).unwrap_or_else(|| format!("Node {:?}", id));
-                                    egui::RichText::new(name).color(Color32::LIGHT_GREEN)
-                                },
-                                Source::Mastery(id) => {
-                                    let name = state.build.tree.nodes_data.get(&id.0).map(|n| n.name.clone()).unwrap_or_else(|| format!("Mastery {:?}", id));
-                                    egui::RichText::new(name).color(Color32::LIGHT_GREEN)
-                                },
-                                Source::Item(slot) => {
-                                    if let Some(item) = state.build.get_equipped(slot) {
-                                        if item.rarity == Rarity::Unique {
-                                            egui::RichText::new(format!("{}", item.name)).color(crate::gui::utils::rarity_to_color(item.rarity))
-                                        } else {
-                                            egui::RichText::new(format!("{slot}")).color(crate::gui::utils::rarity_to_color(item.rarity))
-                                        }
-                                    } else {
-                                        egui::RichText::new(format!("{:?}", slot))
-                                    }
-                                },
-                                Source::Gem(gem_name) => egui::RichText::new(gem_name),
-                                Source::Custom(custom) => egui::RichText::new(custom),
-                            };
+                            let source_text = source_text(state, m.source);
                             ui.add(egui::Label::new(source_text).wrap_mode(egui::TextWrapMode::Extend));
                         });
                         row.col(|ui| {
@@ -166,4 +158,94 @@ fn draw_stat_breakdown(ui: &mut egui::Ui, state: &State, stat_id: StatId) {
                 }
             });
     });
+
+    if let Some(trace) = state.defence_traces.get(&stat_id) {
+        ui.add_space(5.0);
+        ui.push_id(format!("calc_trace_{:?}", stat_id), |ui| {
+            egui::CollapsingHeader::new("Trace").show(ui, |ui| {
+                draw_stat_trace(ui, state, trace);
+            });
+        });
+    }
+}
+
+fn draw_stat_trace(ui: &mut egui::Ui, state: &State, trace: &StatTrace) {
+    if trace.mods.is_empty() {
+        ui.label(egui::RichText::new("No mods").italics());
+    }
+    for (i, mod_trace) in trace.mods.iter().enumerate() {
+        ui.push_id(i, |ui| {
+            draw_mod_trace(ui, state, mod_trace);
+        });
+    }
+}
+
+fn draw_mod_trace(ui: &mut egui::Ui, state: &State, mod_trace: &ModTrace) {
+    let m = &mod_trace.m;
+    ui.horizontal(|ui| {
+        match mod_trace.amount_after {
+            Some(amount_after) if amount_after != mod_trace.amount_before => {
+                ui.label(format!("{} -> {}", mod_trace.amount_before, amount_after));
+            },
+            Some(amount_after) => {
+                ui.label(amount_after.to_string());
+            },
+            None => {
+                ui.label(egui::RichText::new(format!("{} skipped", mod_trace.amount_before)).color(Color32::LIGHT_RED));
+            },
+        }
+        ui.label(format!("{:?}", m.typ));
+        ui.label(source_text(state, m.source));
+    });
+
+    if mod_trace.conditions.is_empty() && mod_trace.inputs.is_empty() {
+        return;
+    }
+
+    ui.indent("mod_trace", |ui| {
+        for (condition, passed) in &mod_trace.conditions {
+            let color = if *passed { Color32::LIGHT_GREEN } else { Color32::LIGHT_RED };
+            ui.label(egui::RichText::new(format!("{:?}", condition)).color(color));
+        }
+        for (input, val) in &mod_trace.inputs {
+            let text = match input {
+                TraceInput::Stat(stat_id) => format!("{} = {}", stat_id, val),
+                TraceInput::StatMult(stat_id) => format!("{} multiplier = {}%", stat_id, *val as f32 / 100.0),
+                TraceInput::Property(prop) => format!("{} = {}", prop, val),
+            };
+            ui.label(text);
+        }
+        for (i, dependency) in mod_trace.dependencies.iter().enumerate() {
+            egui::CollapsingHeader::new(dependency.stat.to_string()).id_salt(i).show(ui, |ui| {
+                draw_stat_trace(ui, state, dependency);
+            });
+        }
+    });
+}
+
+fn source_text(state: &State, source: Source) -> egui::RichText {
+    match source {
+        Source::Innate => egui::RichText::new("Innate"),
+        Source::Node(id) => {
+            let name = state.build.tree.nodes_data.get(&id).map(|n| n.name.clone()).unwrap_or_else(|| format!("Node {:?}", id));
+            egui::RichText::new(name).color(Color32::LIGHT_GREEN)
+        },
+        Source::Mastery(id) => {
+            let name = state.build.tree.nodes_data.get(&id.0).map(|n| n.name.clone()).unwrap_or_else(|| format!("Mastery {:?}", id));
+            egui::RichText::new(name).color(Color32::LIGHT_GREEN)
+        },
+        Source::Item(slot) => {
+            if let Some(item) = state.build.get_equipped(slot) {
+                if item.rarity == Rarity::Unique {
+                    egui::RichText::new(format!("{}", item.name)).color(crate::gui::utils::rarity_to_color(item.rarity))
+                } else {
+                    egui::RichText::new(format!("{slot}")).color(crate::gui::utils::rarity_to_color(item.rarity))
+                }
+            } else {
+                egui::RichText::new(format!("{:?}", slot))
+            }
+        },
+        Source::Gem(gem_name) => egui::RichText::new(gem_name),
+        Source::Custom(custom) => egui::RichText::new(custom),
+    }
 }
@@ -1,5 +1,7 @@
 use lazy_static::lazy_static;
-use lightning_model::build::property;
+use lightning_model::build::enemy::{EnemyPreset, EnemyValue};
+use lightning_model::build::{parse_map_mod, property};
+use strum::IntoEnumIterator;
 use crate::gui::State;
 
 lazy_static! {
@@ -9,13 +11,22 @@ lazy_static! {
         (property::Int::EnduranceCharges, "Endurance Charges"),
         (property::Int::Rage, "Rage"),
         (property::Int::Fortification, "Fortification"),
+        (property::Int::ImpaleStacks, "Impales on Enemy"),
+        (property::Int::ActiveTotems, "Active Totems"),
+        (property::Int::ActiveTraps, "Traps triggered at once"),
+        (property::Int::ActiveMines, "Mines detonated at once"),
     ];
     static ref PROPERTIES_BOOL: Vec<(property::Bool, &'static str)> = vec![
         (property::Bool::Blinded, "Are you Blind?"),
         (property::Bool::Onslaught, "Do you have Onslaught?"),
+        (property::Bool::ArcaneSurge, "Do you have Arcane Surge?"),
+        (property::Bool::Elusive, "Are you Elusive?"),
+        (property::Bool::Tailwind, "Do you have Tailwind?"),
+        (property::Bool::UnholyMight, "Do you have Unholy Might?"),
+        (property::Bool::Adrenaline, "Do you have Adrenaline?"),
         (property::Bool::DealtCritRecently, "Dealt a Crit Recently?"),
         (property::Bool::BlockedRecently, "Blocked Recently?"),
-        (property::Bool::Leeching, "Are you Leeching?"),
+        (property::Bool::Leeching, "Are you always Leeching?"),
         (property::Bool::OnFullLife, "Are you on Full Life?"),
         (property::Bool::OnFullEnergyShield, "Are you on Full ES?"),
         (property::Bool::OnLowLife, "Are you on Low Life?"),
@@ -68,6 +79,71 @@ pub fn draw(ctx: &egui::Context, state: &mut State) {
                         });
                     });
                 });
+                flex.add_ui(egui_flex::item(), |ui| {
+                    egui::Frame::default().inner_margin(4.0).fill(egui::Color32::BLACK).show(ui, |ui| {
+                        egui::ComboBox::from_id_salt("enemy_preset")
+                            .selected_text(state.build.enemy.preset.as_ref())
+                            .show_ui(ui, |ui| {
+                                ui.spacing_mut().item_spacing = egui::Vec2::ZERO;
+                                for preset in EnemyPreset::iter() {
+                                    if ui.selectable_label(preset == state.build.enemy.preset, preset.as_ref()).clicked() {
+                                        state.build.enemy.preset = preset;
+                                        state.request_recalc = true;
+                                    }
+                                }
+                            }
+                        );
+                        egui::Grid::new("grid_ui_enemy").show(ui, |ui| {
+                            for value in EnemyValue::iter() {
+                                let mut val = state.build.enemy.value(value);
+                                ui.label(value.to_string());
+                                if ui.add(egui::DragValue::new(&mut val)).changed() {
+                                    state.build.enemy.set_override(value, val);
+                                    state.request_recalc = true;
+                                }
+                                let mut overridden = state.build.enemy.is_overridden(value);
+                                if ui.checkbox(&mut overridden, "").changed() {
+                                    match overridden {
+                                        true => state.build.enemy.set_override(value, val),
+                                        false => state.build.enemy.clear_override(value),
+                                    }
+                                    state.request_recalc = true;
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    });
+                });
+                flex.add_ui(egui_flex::item(), |ui| {
+                    egui::Frame::default().inner_margin(4.0).fill(egui::Color32::BLACK).show(ui, |ui| {
+                        ui.label("Map Mods");
+                        let mut remove = None;
+                        egui::Grid::new("grid_ui_map_mods").show(ui, |ui| {
+                            for i in 0..state.build.map_mods().len() {
+                                let mut map_mod = state.build.map_mods()[i].clone();
+                                let color = match parse_map_mod(&map_mod) {
+                                    Some(_) => egui::Color32::LIGHT_GRAY,
+                                    None => egui::Color32::LIGHT_RED,
+                                };
+                                if ui.add(egui::TextEdit::singleline(&mut map_mod).text_color(color).desired_width(300.0)).changed() {
+                                    state.build.map_mods_mut()[i] = map_mod;
+                                    state.request_recalc = true;
+                                }
+                                if ui.button("x").clicked() {
+                                    remove = Some(i);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                        if let Some(i) = remove {
+                            state.build.map_mods_mut().remove(i);
+                            state.request_recalc = true;
+                        }
+                        if ui.button("Add Map Mod").clicked() {
+                            state.build.map_mods_mut().push(String::new());
+                        }
+                    });
+                });
             });
         });
 }
@@ -1,4 +1,5 @@
 use crate::gui::{MainState, State, UiState};
+use lightning_model::calc;
 use thousands::Separable;
 use super::{text_gemlink, text_gemlink_cutoff};
 
@@ -32,6 +33,8 @@ fn calc_result_color(label: &str) -> egui::Color32 {
         "Maximum Mana" => egui::Color32::LIGHT_BLUE,
         "Life Regeneration" => egui::Color32::LIGHT_RED,
         "Mana Regeneration" => egui::Color32::LIGHT_BLUE,
+        "Life Recovery/s" => egui::Color32::LIGHT_RED,
+        "Mana Recovery/s" => egui::Color32::LIGHT_BLUE,
         "Strength" => egui::Color32::LIGHT_RED,
 
         "Fire Resistance" => egui::Color32::RED,
@@ -61,6 +64,8 @@ fn val_format(label: &str, val: i64, fmt: Format) -> String {
         Format::Flat => {
             match label {
                 "Speed" => format!("{:.2}", 1000.0 / val as f32),
+                "Ignite Duration" | "Poison Duration" | "Shock Duration" | "Chill Duration" | "Freeze Duration" | "Trigger Cooldown" | "Totem Placement Time" | "ES Recharge Delay" | "Ward Restoration Time" => format!("{:.2}s", val as f32 / 1000.0),
+                "Poison Stacks" => format!("{:.2}", val as f32 / 100.0),
                 _ => val.separate_with_commas(),
             }
         }
@@ -122,6 +127,16 @@ pub fn draw(ctx: &egui::Context, state: &mut State) {
                         if ui.add_enabled(state.ui_state != UiState::Main(MainState::Calc), egui::Button::new("Calc")).clicked() { state.ui_state = UiState::Main(MainState::Calc); }
                         ui.end_row();
                     });
+                    if !state.diagnostics.is_empty() {
+                        egui::CollapsingHeader::new(egui::RichText::new(format!("{} Warnings", state.diagnostics.len())).color(egui::Color32::YELLOW))
+                            .id_salt("diagnostics")
+                            .show(ui, |ui| {
+                                ui.label(egui::RichText::new("Some numbers are incomplete").italics());
+                                for diagnostic in state.diagnostics.iter() {
+                                    ui.colored_label(egui::Color32::YELLOW, diagnostic.to_string());
+                                }
+                            });
+                    }
                     egui::ComboBox::from_id_salt("combo_gemlink")
                         .selected_text(selected_text_gemlink(state))
                         .width(ui.available_width())
@@ -150,25 +165,92 @@ pub fn draw(ctx: &egui::Context, state: &mut State) {
                             }
                         }
                     );
+                    egui::Grid::new("grid_build_offence_calc").show(ui, |ui| {
+                        draw_calc_result_row(ui, "Full DPS", Some(&state.offence_calc.full_dps), Format::Flat);
+                        for skill in &state.offence_calc.skills {
+                            let label = match (skill.full_dps, skill.uptime) {
+                                (false, _) => format!("{} (not counted)", skill.name),
+                                (true, 100) => skill.name.to_string(),
+                                (true, uptime) => format!("{} ({uptime}% uptime)", skill.name),
+                            };
+                            draw_calc_result_row(ui, &label, Some(&skill.dps), Format::Flat);
+                        }
+                    });
                     egui::Grid::new("grid_active_skill_calc").show(ui, |ui| {
                         draw_calc_result_row(ui, "Average Damage", state.active_skill_calc.get("Average Damage"), Format::Flat);
                         draw_calc_result_row(ui, "DPS", state.active_skill_calc.get("DPS"), Format::Flat);
                         draw_calc_result_row(ui, "Speed", state.active_skill_calc.get("Speed"), Format::Flat);
+                        draw_calc_result_row(ui, "Trigger Cooldown", state.active_skill_calc.get("Trigger Cooldown"), Format::Flat);
+                        draw_calc_result_row(ui, "Active Totems", state.active_skill_calc.get("Active Totems"), Format::Flat);
+                        draw_calc_result_row(ui, "Totem Placement Time", state.active_skill_calc.get("Totem Placement Time"), Format::Flat);
+                        draw_calc_result_row(ui, "Active Traps", state.active_skill_calc.get("Active Traps"), Format::Flat);
+                        draw_calc_result_row(ui, "Traps per Throw", state.active_skill_calc.get("Traps per Throw"), Format::Flat);
+                        draw_calc_result_row(ui, "Active Mines", state.active_skill_calc.get("Active Mines"), Format::Flat);
+                        draw_calc_result_row(ui, "Mines per Throw", state.active_skill_calc.get("Mines per Throw"), Format::Flat);
                         draw_calc_result_row(ui, "Chance to Hit (MH)", state.active_skill_calc.get("Chance to Hit (MH)"), Format::Percent);
                         draw_calc_result_row(ui, "Chance to Hit (OH)", state.active_skill_calc.get("Chance to Hit (OH)"), Format::Percent);
                         draw_calc_result_row(ui, "Crit Chance", state.active_skill_calc.get("Crit Chance"), Format::Percent100);
                         draw_calc_result_row(ui, "Crit Chance (MH)", state.active_skill_calc.get("Crit Chance (MH)"), Format::Percent100);
                         draw_calc_result_row(ui, "Crit Chance (OH)", state.active_skill_calc.get("Crit Chance (OH)"), Format::Percent100);
                         draw_calc_result_row(ui, "Crit Multi", state.active_skill_calc.get("Crit Multi"), Format::Percent);
+                        draw_calc_result_row(ui, "Exert More Damage", state.active_skill_calc.get("Exert More Damage"), Format::Percent);
+                        draw_calc_result_row(ui, "DoT DPS", state.active_skill_calc.get("DoT DPS"), Format::Flat);
                         draw_calc_result_row(ui, "Bleed DPS", state.active_skill_calc.get("Bleed DPS"), Format::Flat);
+                        draw_calc_result_row(ui, "Ignite DPS", state.active_skill_calc.get("Ignite DPS"), Format::Flat);
+                        draw_calc_result_row(ui, "Ignite Chance", state.active_skill_calc.get("Ignite Chance"), Format::Percent);
+                        draw_calc_result_row(ui, "Ignite Duration", state.active_skill_calc.get("Ignite Duration"), Format::Flat);
+                        draw_calc_result_row(ui, "Poison DPS", state.active_skill_calc.get("Poison DPS"), Format::Flat);
+                        draw_calc_result_row(ui, "Poison Stacks", state.active_skill_calc.get("Poison Stacks"), Format::Flat);
+                        draw_calc_result_row(ui, "Poison Duration", state.active_skill_calc.get("Poison Duration"), Format::Flat);
+                        draw_calc_result_row(ui, "Impale DPS", state.active_skill_calc.get("Impale DPS"), Format::Flat);
+                        draw_calc_result_row(ui, "Impale Chance", state.active_skill_calc.get("Impale Chance"), Format::Percent);
+                        draw_calc_result_row(ui, "Shock Effect", state.active_skill_calc.get("Shock Effect"), Format::Percent);
+                        draw_calc_result_row(ui, "Shock Chance", state.active_skill_calc.get("Shock Chance"), Format::Percent);
+                        draw_calc_result_row(ui, "Shock Duration", state.active_skill_calc.get("Shock Duration"), Format::Flat);
+                        draw_calc_result_row(ui, "Chill Effect", state.active_skill_calc.get("Chill Effect"), Format::Percent);
+                        draw_calc_result_row(ui, "Chill Duration", state.active_skill_calc.get("Chill Duration"), Format::Flat);
+                        draw_calc_result_row(ui, "Freeze Chance", state.active_skill_calc.get("Freeze Chance"), Format::Percent);
+                        draw_calc_result_row(ui, "Freeze Duration", state.active_skill_calc.get("Freeze Duration"), Format::Flat);
+                        draw_calc_result_row(ui, "Minion DPS", state.active_skill_calc.get("Minion DPS"), Format::Flat);
+                        draw_calc_result_row(ui, "Minion Count", state.active_skill_calc.get("Minion Count"), Format::Flat);
+                        draw_calc_result_row(ui, "Total Minion DPS", state.active_skill_calc.get("Total Minion DPS"), Format::Flat);
+                        draw_calc_result_row(ui, "Minion Life", state.active_skill_calc.get("Minion Life"), Format::Flat);
+                        draw_calc_result_row(ui, "Minion Fire Resistance", state.active_skill_calc.get("Minion Fire Resistance"), Format::Percent);
+                        draw_calc_result_row(ui, "Minion Cold Resistance", state.active_skill_calc.get("Minion Cold Resistance"), Format::Percent);
+                        draw_calc_result_row(ui, "Minion Lightning Resistance", state.active_skill_calc.get("Minion Lightning Resistance"), Format::Percent);
+                        draw_calc_result_row(ui, "Minion Chaos Resistance", state.active_skill_calc.get("Minion Chaos Resistance"), Format::Percent);
+                        draw_calc_result_row(ui, "Life Leech/s", state.active_skill_calc.get("Life Leech/s"), Format::Flat);
+                        draw_calc_result_row(ui, "Mana Leech/s", state.active_skill_calc.get("Mana Leech/s"), Format::Flat);
+                        draw_calc_result_row(ui, "ES Leech/s", state.active_skill_calc.get("ES Leech/s"), Format::Flat);
                         draw_calc_result_row(ui, "Mana Cost", state.active_skill_calc.get("Mana Cost"), Format::Flat);
+                        draw_calc_result_row(ui, "Mana Cost/s", state.active_skill_calc.get("Mana Cost/s"), Format::Flat);
+                        draw_calc_result_row(ui, "Net Mana/s", state.active_skill_calc.get("Net Mana/s"), Format::Flat);
                     });
+                    for warning in calc::calc_resource_warnings(&state.defence_calc, &state.active_skill_calc) {
+                        ui.colored_label(egui::Color32::YELLOW, warning);
+                    }
                     ui.separator();
                     egui::Grid::new("grid_defence_calc_life").show(ui, |ui| {
                         draw_calc_result_row(ui, "Maximum Life", state.defence_calc.get("Maximum Life"), Format::Flat);
                         draw_calc_result_row(ui, "Maximum Mana", state.defence_calc.get("Maximum Mana"), Format::Flat);
                         draw_calc_result_row(ui, "Life Regeneration", state.defence_calc.get("Life Regeneration"), Format::Flat);
                         draw_calc_result_row(ui, "Mana Regeneration", state.defence_calc.get("Mana Regeneration"), Format::Flat);
+                        draw_calc_result_row(ui, "ES Regeneration", state.defence_calc.get("ES Regeneration"), Format::Flat);
+                        draw_calc_result_row(ui, "ES Recharge", state.defence_calc.get("ES Recharge"), Format::Flat);
+                        draw_calc_result_row(ui, "ES Recharge Delay", state.defence_calc.get("ES Recharge Delay"), Format::Flat);
+                        draw_calc_result_row(ui, "Ward", state.defence_calc.get("Ward"), Format::Flat);
+                        draw_calc_result_row(ui, "Ward Restoration Time", state.defence_calc.get("Ward Restoration Time"), Format::Flat);
+                        draw_calc_result_row(ui, "Reserved Life", state.defence_calc.get("Reserved Life"), Format::Flat);
+                        draw_calc_result_row(ui, "Unreserved Life", state.defence_calc.get("Unreserved Life"), Format::Flat);
+                        draw_calc_result_row(ui, "Reserved Mana", state.defence_calc.get("Reserved Mana"), Format::Flat);
+                        draw_calc_result_row(ui, "Unreserved Mana", state.defence_calc.get("Unreserved Mana"), Format::Flat);
+                    });
+                    ui.separator();
+                    egui::Grid::new("grid_defence_calc_recovery").show(ui, |ui| {
+                        let recovery = calc::calc_recovery(&state.defence_calc, &state.active_skill_calc);
+                        draw_calc_result_row(ui, "Life Recovery/s", recovery.get("Life Recovery/s"), Format::Flat);
+                        draw_calc_result_row(ui, "Mana Recovery/s", recovery.get("Mana Recovery/s"), Format::Flat);
+                        draw_calc_result_row(ui, "ES Recovery/s", recovery.get("ES Recovery/s"), Format::Flat);
                     });
                     ui.separator();
                     egui::Grid::new("grid_defence_calc_res").show(ui, |ui| {
@@ -180,17 +262,36 @@ pub fn draw(ctx: &egui::Context, state: &mut State) {
                     ui.separator();
                     egui::Grid::new("grid_defence_calc_def").show(ui, |ui| {
                         draw_calc_result_row(ui, "Armour", state.defence_calc.get("Armour"), Format::Flat);
+                        draw_calc_result_row(ui, "Physical Damage Reduction", state.defence_calc.get("Physical Damage Reduction"), Format::Percent);
+                        draw_calc_result_row(ui, "Enemy Hit Size", state.defence_calc.get("Enemy Hit Size"), Format::Flat);
                         draw_calc_result_row(ui, "Evasion", state.defence_calc.get("Evasion"), Format::Flat);
+                        draw_calc_result_row(ui, "Chance to Evade", state.defence_calc.get("Chance to Evade"), Format::Percent);
                         draw_calc_result_row(ui, "Energy Shield", state.defence_calc.get("Energy Shield"), Format::Flat);
                         draw_calc_result_row(ui, "Attack Block", state.defence_calc.get("Block"), Format::Percent);
                         draw_calc_result_row(ui, "Spell Block", state.defence_calc.get("Spell Block"), Format::Percent);
                         draw_calc_result_row(ui, "Spell Suppression", state.defence_calc.get("Spell Suppression"), Format::Percent);
+                        draw_calc_result_row(ui, "Chance to Avoid Attacks", state.defence_calc.get("Chance to Avoid Attacks"), Format::Percent);
+                        draw_calc_result_row(ui, "Chance to Avoid Spells", state.defence_calc.get("Chance to Avoid Spells"), Format::Percent);
+                    });
+                    ui.separator();
+                    egui::Grid::new("grid_defence_calc_max_hit").show(ui, |ui| {
+                        draw_calc_result_row(ui, "Max Physical Hit", state.defence_calc.get("Max Physical Hit"), Format::Flat);
+                        draw_calc_result_row(ui, "Max Fire Hit", state.defence_calc.get("Max Fire Hit"), Format::Flat);
+                        draw_calc_result_row(ui, "Max Cold Hit", state.defence_calc.get("Max Cold Hit"), Format::Flat);
+                        draw_calc_result_row(ui, "Max Lightning Hit", state.defence_calc.get("Max Lightning Hit"), Format::Flat);
+                        draw_calc_result_row(ui, "Max Chaos Hit", state.defence_calc.get("Max Chaos Hit"), Format::Flat);
+                        draw_calc_result_row(ui, "Max Physical Spell Hit", state.defence_calc.get("Max Physical Spell Hit"), Format::Flat);
+                        draw_calc_result_row(ui, "Max Fire Spell Hit", state.defence_calc.get("Max Fire Spell Hit"), Format::Flat);
+                        draw_calc_result_row(ui, "Max Cold Spell Hit", state.defence_calc.get("Max Cold Spell Hit"), Format::Flat);
+                        draw_calc_result_row(ui, "Max Lightning Spell Hit", state.defence_calc.get("Max Lightning Spell Hit"), Format::Flat);
+                        draw_calc_result_row(ui, "Max Chaos Spell Hit", state.defence_calc.get("Max Chaos Spell Hit"), Format::Flat);
                     });
                     ui.separator();
                     egui::Grid::new("grid_defence_calc_stats").show(ui, |ui| {
                         draw_calc_result_row(ui, "Strength", state.defence_calc.get("Strength"), Format::Flat);
                         draw_calc_result_row(ui, "Dexterity", state.defence_calc.get("Dexterity"), Format::Flat);
                         draw_calc_result_row(ui, "Intelligence", state.defence_calc.get("Intelligence"), Format::Flat);
+                        draw_calc_result_row(ui, "Movement Speed", state.defence_calc.get("Movement Speed"), Format::Percent);
                     });
                 });
             });
@@ -179,6 +179,8 @@ pub fn draw(ctx: &egui::Context, state: &mut State) {
                                     .column(Column::auto().at_least(28.0))
                                     .column(Column::auto())
                                     .column(Column::auto())
+                                    .column(Column::auto())
+                                    .column(Column::auto())
                                     .vscroll(false)
                                     .header(14.0, |mut header| {
                                         header.col(|_| {
@@ -195,6 +197,12 @@ pub fn draw(ctx: &egui::Context, state: &mut State) {
                                         header.col(|ui| {
                                             ui.strong("Enabled");
                                         });
+                                        header.col(|ui| {
+                                            ui.strong("Full DPS");
+                                        });
+                                        header.col(|ui| {
+                                            ui.strong("Uptime %");
+                                        });
                                     });
 
                                 table.body(|mut body| {
@@ -238,6 +246,28 @@ pub fn draw(ctx: &egui::Context, state: &mut State) {
                                                     action = Some(Action::SwapGem((i, Arc::new(new_gem))));
                                                 }
                                             });
+                                            // Full DPS
+                                            row.col(|ui| {
+                                                if socketed_gem.data().active_skill.is_some() {
+                                                    let mut full_dps = socketed_gem.full_dps;
+                                                    if ui.checkbox(&mut full_dps, "").clicked() {
+                                                        let mut new_gem = (**socketed_gem).clone();
+                                                        new_gem.full_dps = full_dps;
+                                                        action = Some(Action::SwapGem((i, Arc::new(new_gem))));
+                                                    }
+                                                }
+                                            });
+                                            // Uptime
+                                            row.col(|ui| {
+                                                if socketed_gem.data().active_skill.is_some() {
+                                                    let mut uptime = socketed_gem.uptime;
+                                                    if ui.add(egui::DragValue::new(&mut uptime).range(RangeInclusive::new(0, 100))).changed() {
+                                                        let mut new_gem = (**socketed_gem).clone();
+                                                        new_gem.uptime = uptime;
+                                                        action = Some(Action::SwapGem((i, Arc::new(new_gem))));
+                                                    }
+                                                }
+                                            });
                                         });
                                     }
                                     // Show empty gem slot
@@ -255,6 +285,10 @@ pub fn draw(ctx: &egui::Context, state: &mut State) {
                                         });
                                         row.col(|_| {
                                         });
+                                        row.col(|_| {
+                                        });
+                                        row.col(|_| {
+                                        });
                                     });
                                 });
                             }
@@ -1,5 +1,5 @@
 use std::{ops::RangeInclusive};
-use lightning_model::{build::{property, BanditChoice, CampaignChoice}, data::TREE, data::tree::Ascendancy};
+use lightning_model::{build::{property, BanditChoice, CampaignChoice, PantheonMajor, PantheonMinor, PANTHEON_MAJOR_STATS, PANTHEON_MINOR_STATS}, data::TREE, data::tree::Ascendancy};
 use strum::IntoEnumIterator;
 use crate::gui::{State, UiState};
 
@@ -96,6 +96,40 @@ pub fn draw(ctx: &egui::Context, state: &mut State) {
                         }
                     }
                 );
+                egui::ComboBox::from_id_salt("pantheon_major")
+                    .selected_text(state.build.pantheon_major.as_ref())
+                    .show_ui(ui, |ui| {
+                        ui.spacing_mut().item_spacing = egui::Vec2::ZERO;
+                        for pantheon_major in PantheonMajor::iter() {
+                            if ui.selectable_label(pantheon_major == state.build.pantheon_major, pantheon_major.as_ref()).clicked() {
+                                state.build.pantheon_major = pantheon_major;
+                                state.build.pantheon_major_upgrades = 0;
+                                state.request_recalc = true;
+                            }
+                        }
+                    }
+                );
+                let major_upgrades = PANTHEON_MAJOR_STATS[&state.build.pantheon_major].len() - 1;
+                if major_upgrades > 0 && draw_pantheon_upgrades(ui, "pantheon_major_upgrades", &mut state.build.pantheon_major_upgrades, major_upgrades) {
+                    state.request_recalc = true;
+                }
+                egui::ComboBox::from_id_salt("pantheon_minor")
+                    .selected_text(state.build.pantheon_minor.as_ref())
+                    .show_ui(ui, |ui| {
+                        ui.spacing_mut().item_spacing = egui::Vec2::ZERO;
+                        for pantheon_minor in PantheonMinor::iter() {
+                            if ui.selectable_label(pantheon_minor == state.build.pantheon_minor, pantheon_minor.as_ref()).clicked() {
+                                state.build.pantheon_minor = pantheon_minor;
+                                state.build.pantheon_minor_upgrades = 0;
+                                state.request_recalc = true;
+                            }
+                        }
+                    }
+                );
+                let minor_upgrades = PANTHEON_MINOR_STATS[&state.build.pantheon_minor].len() - 1;
+                if minor_upgrades > 0 && draw_pantheon_upgrades(ui, "pantheon_minor_upgrades", &mut state.build.pantheon_minor_upgrades, minor_upgrades) {
+                    state.request_recalc = true;
+                }
                 egui::ComboBox::from_id_salt("campaign_choice")
                     .selected_text(state.build.campaign_choice.as_ref())
                     .show_ui(ui, |ui| {
@@ -121,3 +155,21 @@ pub fn draw(ctx: &egui::Context, state: &mut State) {
             });
         });
 }
+
+/// Returns true if the number of upgrades was changed
+fn draw_pantheon_upgrades(ui: &mut egui::Ui, id: &str, upgrades: &mut usize, max: usize) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(format!("Upgrades: {}", upgrades))
+        .show_ui(ui, |ui| {
+            ui.spacing_mut().item_spacing = egui::Vec2::ZERO;
+            for i in 0..=max {
+                if ui.selectable_label(i == *upgrades, format!("Upgrades: {}", i)).clicked() {
+                    *upgrades = i;
+                    changed = true;
+                }
+            }
+        }
+    );
+    changed
+}
@@ -51,6 +51,7 @@ fn draw_hover_window(ctx: &egui::Context, state: &mut State) {
                             draw_item(
                                 ui,
                                 item,
+                                &state.defence_stats,
                                 Source::Item(Slot::TreeJewel(node.skill)),
                                 state.config.show_debug,
                             );
@@ -1,5 +1,5 @@
 use enumflags2::{BitFlags, make_bitflags};
-use lightning_model::{data::{DAMAGE_GROUPS, base_item::Rarity, gem::{GemData, GemTag}}, item::Item, modifier::Source};
+use lightning_model::{build::stat::Stats, data::{DAMAGE_GROUPS, base_item::Rarity, gem::{GemData, GemTag}}, item::Item, modifier::Source};
 use lightning_model::gem::Gem;
 
 pub const COLOR_INT: egui::Color32 = egui::Color32::from_rgb(0x67, 0x67, 0xEA);
@@ -106,7 +106,8 @@ pub fn draw_gem(ui: &mut egui::Ui, gem: &Gem) {
     });
 }
 
-pub fn draw_item(ui: &mut egui::Ui, item: &Item, source: Source, show_debug: bool) {
+/// `stats` are the player's, for the global mods changing flasks
+pub fn draw_item(ui: &mut egui::Ui, item: &Item, stats: &Stats, source: Source, show_debug: bool) {
     ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
         if !item.name.is_empty() {
             ui.label(egui::RichText::new(&item.name).color(rarity_to_color(item.rarity)).size(20.0));
@@ -145,6 +146,22 @@ pub fn draw_item(ui: &mut egui::Ui, item: &Item, source: Source, show_debug: boo
             }
             ui.separator();
         }
+        if let Some(flask) = item.calc_flask(stats) {
+            if flask.life > 0 {
+                ui.label(format!("Recovers {} Life over {:.2} Seconds", flask.life, flask.duration as f32 / 1000.0));
+            }
+            if flask.mana > 0 {
+                ui.label(format!("Recovers {} Mana over {:.2} Seconds", flask.mana, flask.duration as f32 / 1000.0));
+            }
+            if flask.life == 0 && flask.mana == 0 {
+                ui.label(format!("Lasts {:.2} Seconds", flask.duration as f32 / 1000.0));
+            }
+            ui.label(format!("Consumes {} of {} Charges on use", flask.charges_per_use, flask.charges_max));
+            if flask.charges_gained != 100 {
+                ui.label(format!("{}% Charges gained", flask.charges_gained));
+            }
+            ui.separator();
+        }
         //ui.spacing_mut().item_spacing = item_spacing;
         if !item.mods_enchant.is_empty() {
             for stat in &item.mods_enchant {
@@ -164,7 +181,7 @@ pub fn draw_item(ui: &mut egui::Ui, item: &Item, source: Source, show_debug: boo
     });
 }
 
-pub fn draw_item_window(ui: &mut egui::Ui, item: &Item, pos: impl Into<egui::Pos2>, show_debug: bool, deltas: Option<&[(String, rustc_hash::FxHashMap<&'static str, i64>)]>) {
+pub fn draw_item_window(ui: &mut egui::Ui, item: &Item, stats: &Stats, pos: impl Into<egui::Pos2>, show_debug: bool, deltas: Option<&[(String, rustc_hash::FxHashMap<&'static str, i64>)]>) {
     let window_id = egui::Id::new("Hover Item")
         .with(&item.name)
         .with(&item.base_item)
@@ -179,7 +196,7 @@ pub fn draw_item_window(ui: &mut egui::Ui, item: &Item, pos: impl Into<egui::Pos
         .fixed_pos(pos)
         .frame(egui::Frame::window(&ui.ctx().style()))
         .show(ui.ctx(), |ui| {
-            draw_item(ui, item, Source::Innate, show_debug);
+            draw_item(ui, item, stats, Source::Innate, show_debug);
             if let Some(deltas) = deltas {
                 draw_item_deltas(ui, deltas);
             }
@@ -52,19 +52,21 @@ fn process_state(state: &mut State) -> Result<(), Box<dyn Error>> {
     state.ui_state = match &ui_state {
         UiState::LoadBuild(path) => {
             state.build = util::load_build(path)?;
+            state.load_diagnostics.clear();
             state.reset();
             println!("Loaded build from {}", &path.display());
             UiState::Main(MainState::Tree)
         }
         #[cfg(feature = "import")]
         UiState::ImportBuild => {
-            state.build = util::fetch_build(&state.import_account, &state.import_character)?;
+            (state.build, state.load_diagnostics) = util::fetch_build(&state.import_account, &state.import_character)?;
             state.reset();
             println!("Fetched build: {} {}", &state.import_account, &state.import_character);
             UiState::Main(MainState::Tree)
         }
         UiState::NewBuild => {
             state.build = build::Build::new_player();
+            state.load_diagnostics.clear();
             state.reset();
             UiState::Main(MainState::Tree)
         }
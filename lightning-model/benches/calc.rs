@@ -15,7 +15,7 @@ fn fetch() -> Result<Build, Box<dyn std::error::Error>> {
         }
     }
 
-    let player = import::character("Ben_#4007", "ben_im_jungroan")?;
+    let (player, _) = import::character("Ben_#4007", "ben_im_jungroan")?;
     serde_json::to_writer(&fs::File::create(BUILD_PATH)?, &player)?;
     Ok(player)
 }
@@ -74,10 +74,10 @@ fn calc_clone_build(bencher: divan::Bencher) {
 fn calc_power_report_maxhp(bencher: divan::Bencher) {
     let player = fetch().expect("Failed to get a build");
     // Initialize caches
-    let _base_maxhp = calc::calc_defence(&player).0["Maximum Life"];
+    let _base_maxhp = calc::calc_defence(&player, false).0["Maximum Life"];
 
     bencher.bench_local(|| {
-        let _ = calc::PowerReport::new_defence(&player, "Maximum Life");
+        let _ = calc::PowerReport::new_defence(&player, "Maximum Life", false);
     });
 }
 
@@ -98,10 +98,10 @@ fn calc_gem(bencher: divan::Bencher) {
 fn calc_defence(bencher: divan::Bencher) {
     let player = fetch().expect("Failed to get a build");
 
-    lightning_model::calc::calc_defence(&player);
+    lightning_model::calc::calc_defence(&player, false);
 
     bencher.bench_local(|| {
-        lightning_model::calc::calc_defence(&player);
+        lightning_model::calc::calc_defence(&player, false);
     });
 }
 
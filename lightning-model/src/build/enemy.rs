@@ -0,0 +1,120 @@
+use crate::data::MONSTER_STATS;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumIter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, AsRefStr, EnumIter)]
+pub enum EnemyPreset {
+    #[default]
+    #[strum(serialize = "Map Monster")]
+    MapMonster,
+    #[strum(serialize = "Map Boss")]
+    MapBoss,
+    #[strum(serialize = "Pinnacle Boss")]
+    PinnacleBoss,
+}
+
+/// Enemy values that can be overridden on top of a preset.
+/// Multipliers are percentages of the default monster stats for the enemy level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, strum_macros::Display)]
+pub enum EnemyValue {
+    #[strum(to_string = "Fire Resistance")]
+    FireResistance,
+    #[strum(to_string = "Cold Resistance")]
+    ColdResistance,
+    #[strum(to_string = "Lightning Resistance")]
+    LightningResistance,
+    #[strum(to_string = "Chaos Resistance")]
+    ChaosResistance,
+    #[strum(to_string = "Armour %")]
+    ArmourMultiplier,
+    #[strum(to_string = "Evasion %")]
+    EvasionMultiplier,
+    #[strum(to_string = "Less Curse Effect")]
+    CurseEffectReduction,
+    #[strum(to_string = "Ailment Threshold %")]
+    AilmentThresholdMultiplier,
+    #[strum(to_string = "Less Damage Taken")]
+    LessDamageTaken,
+    #[strum(to_string = "Hit Damage %")]
+    HitDamageMultiplier,
+    /// Replaces the hit from the monster damage of the enemy level when not 0
+    #[strum(to_string = "Hit Size (0: from level)")]
+    HitSize,
+}
+
+impl EnemyPreset {
+    pub fn value(self, v: EnemyValue) -> i64 {
+        match (self, v) {
+            (EnemyPreset::MapMonster, EnemyValue::FireResistance | EnemyValue::ColdResistance | EnemyValue::LightningResistance | EnemyValue::ChaosResistance) => 0,
+            (EnemyPreset::MapMonster, EnemyValue::CurseEffectReduction) => 0,
+            (EnemyPreset::MapBoss, EnemyValue::FireResistance | EnemyValue::ColdResistance | EnemyValue::LightningResistance) => 40,
+            (EnemyPreset::MapBoss, EnemyValue::ChaosResistance) => 25,
+            (EnemyPreset::MapBoss, EnemyValue::CurseEffectReduction) => 33,
+            (EnemyPreset::MapBoss, EnemyValue::AilmentThresholdMultiplier) => 500,
+            (EnemyPreset::PinnacleBoss, EnemyValue::FireResistance | EnemyValue::ColdResistance | EnemyValue::LightningResistance) => 50,
+            (EnemyPreset::PinnacleBoss, EnemyValue::ChaosResistance) => 30,
+            (EnemyPreset::PinnacleBoss, EnemyValue::ArmourMultiplier | EnemyValue::EvasionMultiplier) => 150,
+            (EnemyPreset::PinnacleBoss, EnemyValue::CurseEffectReduction) => 66,
+            (EnemyPreset::PinnacleBoss, EnemyValue::AilmentThresholdMultiplier) => 2000,
+            (EnemyPreset::MapBoss, EnemyValue::HitDamageMultiplier) => 300,
+            (EnemyPreset::PinnacleBoss, EnemyValue::HitDamageMultiplier) => 600,
+            (_, EnemyValue::ArmourMultiplier | EnemyValue::EvasionMultiplier | EnemyValue::AilmentThresholdMultiplier | EnemyValue::HitDamageMultiplier) => 100,
+            (_, EnemyValue::LessDamageTaken | EnemyValue::HitSize) => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnemyConfig {
+    pub preset: EnemyPreset,
+    overrides: FxHashMap<EnemyValue, i64>,
+}
+
+impl EnemyConfig {
+    pub fn value(&self, v: EnemyValue) -> i64 {
+        self.overrides.get(&v).copied().unwrap_or_else(|| self.preset.value(v))
+    }
+
+    pub fn is_overridden(&self, v: EnemyValue) -> bool {
+        self.overrides.contains_key(&v)
+    }
+
+    pub fn set_override(&mut self, v: EnemyValue, val: i64) {
+        self.overrides.insert(v, val);
+    }
+
+    pub fn clear_override(&mut self, v: EnemyValue) {
+        self.overrides.remove(&v);
+    }
+
+    /// Physical damage of an enemy hit, used to compare against the player's armour
+    pub fn hit_size(&self, level: i64) -> i64 {
+        match self.value(EnemyValue::HitSize) {
+            0 => MONSTER_STATS.get(&level).map_or(0, |m| m.physical_damage as i64 * self.value(EnemyValue::HitDamageMultiplier) / 100),
+            hit_size => hit_size,
+        }
+    }
+}
+
+#[test]
+fn test_enemy_overrides() {
+    let mut enemy = EnemyConfig { preset: EnemyPreset::PinnacleBoss, ..Default::default() };
+    assert_eq!(enemy.value(EnemyValue::FireResistance), 50);
+    assert_eq!(enemy.value(EnemyValue::LessDamageTaken), 0);
+
+    // Overrides replace the preset value until cleared, even across preset changes
+    enemy.set_override(EnemyValue::FireResistance, -20);
+    enemy.preset = EnemyPreset::MapBoss;
+    assert!(enemy.is_overridden(EnemyValue::FireResistance));
+    assert_eq!(enemy.value(EnemyValue::FireResistance), -20);
+    assert_eq!(enemy.value(EnemyValue::ColdResistance), 40);
+    enemy.clear_override(EnemyValue::FireResistance);
+    assert_eq!(enemy.value(EnemyValue::FireResistance), 40);
+
+    // Hit size comes from the monster damage of the level, scaled by the preset, unless set
+    let level_damage = MONSTER_STATS[&83].physical_damage as i64;
+    assert_eq!(enemy.hit_size(83), level_damage * 3);
+    enemy.set_override(EnemyValue::HitSize, 5000);
+    assert_eq!(enemy.hit_size(83), 5000);
+}
@@ -1,14 +1,52 @@
 use enumflags2::BitFlags;
 use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::{build::{Build, Defence, Slot, property, stat::{self, Stat, StatId}}, data::gem::GemTag, modifier::{Condition, Mod, ModFlag, Mutation}};
+use crate::{build::{Build, Defence, Slot, property, stat::{self, Stat, StatId}}, data::gem::GemTag, diagnostics::{Diagnostic, Diagnostics}, modifier::{Condition, Mod, ModFlag, Mutation, Source}};
+
+/// Value read while evaluating a mod
+#[derive(Debug, Clone, Copy)]
+pub enum TraceInput {
+    Stat(StatId),
+    /// Multiplier of the stat's increases and reductions, in hundredths of a percent
+    StatMult(StatId),
+    Property(property::Int),
+}
+
+/// How a single mod was evaluated
+#[derive(Debug, Clone)]
+pub struct ModTrace {
+    pub m: Mod,
+    /// Each condition of the mod and whether it passed, one passing condition is enough
+    pub conditions: Vec<(Condition, bool)>,
+    /// Stats and properties read by the conditions and mutations, with the value read
+    pub inputs: Vec<(TraceInput, i64)>,
+    pub amount_before: i64,
+    /// None when the conditions failed and the mod was skipped
+    pub amount_after: Option<i64>,
+    /// Derivation of the stats in `inputs`, filled in by `Evaluator::trace_tree`
+    pub dependencies: Vec<StatTrace>,
+}
+
+/// Derivation tree of a stat
+#[derive(Debug, Clone)]
+pub struct StatTrace {
+    pub stat: StatId,
+    pub mods: Vec<ModTrace>,
+}
 
 /// Evaluate Stats from a collection of Mods
 pub struct Evaluator<'a> {
     build: &'a Build,
     pub mods_by_stat: FxHashMap<StatId, Vec<&'a Mod>>,
     pub resolved_stats: FxHashMap<StatId, Stat>,
-    evaluating: FxHashSet<StatId>,
+    /// Stats currently being evaluated, outermost first
+    evaluating: Vec<StatId>,
+    pub diagnostics: Diagnostics,
+    /// Whether the skill leeches, see `calc::is_leeching`
+    leeching: bool,
+    trace: Option<FxHashMap<StatId, Vec<ModTrace>>>,
+    /// Inputs read by each mod being evaluated, innermost last
+    trace_inputs: Vec<Vec<(TraceInput, i64)>>,
 }
 
 impl<'a> Evaluator<'a> {
@@ -27,32 +65,94 @@ impl<'a> Evaluator<'a> {
             build,
             mods_by_stat,
             resolved_stats: FxHashMap::default(),
-            evaluating: FxHashSet::default(),
+            evaluating: vec![],
+            diagnostics: Diagnostics::default(),
+            leeching: false,
+            trace: None,
+            trace_inputs: vec![],
+        }
+    }
+
+    /// Enables the mods applying while leeching
+    pub fn with_leeching(mut self, leeching: bool) -> Self {
+        self.leeching = leeching;
+        self
+    }
+
+    /// Records how every mod gets evaluated, see `trace_tree`
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(FxHashMap::default());
+        self
+    }
+
+    /// Derivation tree of an evaluated stat, None unless tracing is enabled
+    pub fn trace_tree(&self, stat_id: StatId) -> Option<StatTrace> {
+        self.trace.as_ref()?;
+        Some(self.build_trace(stat_id, &mut FxHashSet::default()))
+    }
+
+    fn build_trace(&self, stat_id: StatId, path: &mut FxHashSet<StatId>) -> StatTrace {
+        path.insert(stat_id);
+        let mut mods = self.trace.as_ref().and_then(|trace| trace.get(&stat_id)).cloned().unwrap_or_default();
+        for mod_trace in mods.iter_mut() {
+            for (input, _) in &mod_trace.inputs {
+                if let TraceInput::Stat(dependency) | TraceInput::StatMult(dependency) = input &&
+                   !path.contains(dependency) {
+                    mod_trace.dependencies.push(self.build_trace(*dependency, path));
+                }
+            }
+        }
+        path.remove(&stat_id);
+        StatTrace { stat: stat_id, mods }
+    }
+
+    fn trace_input(&mut self, input: TraceInput, val: i64) {
+        if let Some(inputs) = self.trace_inputs.last_mut() {
+            inputs.push((input, val));
         }
     }
 
     pub fn get_stat_val(&mut self, stat_id: StatId) -> i64 {
-        self.eval_stat(stat_id).val()
+        let val = self.eval_stat(stat_id).val();
+        self.trace_input(TraceInput::Stat(stat_id), val);
+        val
     }
 
     pub fn get_stat_mult(&mut self, stat_id: StatId) -> i64 {
-        self.eval_stat(stat_id).mult()
+        let mult = self.eval_stat(stat_id).mult();
+        self.trace_input(TraceInput::StatMult(stat_id), mult);
+        mult
     }
 
     pub fn eval_stat(&mut self, stat_id: StatId) -> &Stat {
         if !self.resolved_stats.contains_key(&stat_id) {
-            if !self.evaluating.insert(stat_id) {
-                eprintln!("Warning: Circular dependency detected for stat: {:?}", stat_id);
+            if let Some(loop_start) = self.evaluating.iter().position(|id| *id == stat_id) {
+                let mut path = self.evaluating[loop_start..].to_vec();
+                path.push(stat_id);
+                self.diagnostics.push(Diagnostic::CircularDependency(path));
                 self.resolved_stats.insert(stat_id, Stat::default());
                 return self.resolved_stats.get(&stat_id).unwrap();
             }
+            self.evaluating.push(stat_id);
 
             let mut current_stat = Stat::default();
             let mods_to_process = self.mods_by_stat.remove(&stat_id).unwrap_or_default();
 
             for m in mods_to_process {
-                let passes_conditions_bor = m.conditions.is_empty() || m.conditions.iter().any(|c| self.check_condition(c));
+                let tracing = self.trace.is_some();
+                let mut conditions = vec![];
+                let passes_conditions_bor = if tracing {
+                    self.trace_inputs.push(vec![]);
+                    // Check every condition so the trace shows all of them
+                    conditions = m.conditions.iter().map(|c| (*c, self.check_condition(c))).collect();
+                    conditions.is_empty() || conditions.iter().any(|(_, passed)| *passed)
+                } else {
+                    m.conditions.is_empty() || m.conditions.iter().any(|c| self.check_condition(c))
+                };
                 if !passes_conditions_bor {
+                    if tracing {
+                        self.record_trace(stat_id, m, conditions, None);
+                    }
                     continue;
                 }
 
@@ -66,16 +166,39 @@ impl<'a> Evaluator<'a> {
                     m.revised_amount = Some((m.final_amount() * mult) / 10000);
                 }
 
+                if let Source::Item(Slot::Flask(_)) = m.source {
+                    let mult = self.get_stat_mult(StatId::FlaskEffect);
+                    m.revised_amount = Some((m.final_amount() * mult) / 10000);
+                }
+
+                if tracing {
+                    self.record_trace(stat_id, &m, conditions, Some(m.final_amount()));
+                }
+
                 current_stat.adjust_mod_move(m);
             }
 
-            self.evaluating.remove(&stat_id);
+            self.evaluating.pop();
             self.resolved_stats.insert(stat_id, current_stat);
         }
 
         self.resolved_stats.get(&stat_id).unwrap()
     }
 
+    fn record_trace(&mut self, stat_id: StatId, m: &Mod, conditions: Vec<(Condition, bool)>, amount_after: Option<i64>) {
+        let inputs = self.trace_inputs.pop().unwrap_or_default();
+        if let Some(trace) = self.trace.as_mut() {
+            trace.entry(stat_id).or_default().push(ModTrace {
+                m: *m,
+                conditions,
+                inputs,
+                amount_before: m.amount,
+                amount_after,
+                dependencies: vec![],
+            });
+        }
+    }
+
     fn property_int_stats(&mut self, p: property::Int) -> i64 {
         let min = match property::int_data(p).min {
             property::Val::Val(i) => i,
@@ -86,10 +209,13 @@ impl<'a> Evaluator<'a> {
             property::Val::Stat(s) => self.get_stat_val(s),
         };
 
-        if self.build.is_property_int_maxed(p) {
-            return max;
-        }
-        self.build.property_int(p).clamp(min, max)
+        let val = if self.build.is_property_int_maxed(p) {
+            max
+        } else {
+            self.build.property_int(p).clamp(min, max)
+        };
+        self.trace_input(TraceInput::Property(p), val);
+        val
     }
 
     fn check_condition(&mut self, c: &Condition) -> bool {
@@ -154,6 +280,12 @@ impl<'a> Evaluator<'a> {
                     return false;
                 }
             }
+            Condition::Leeching => {
+                if !self.leeching && !self.build.property_bool(property::Bool::Leeching) { return false; }
+            }
+            Condition::UsingFlask => {
+                if !self.build.has_active_flask() { return false; }
+            }
         }
         true
     }
@@ -206,3 +338,35 @@ impl<'a> Evaluator<'a> {
         m.revised_amount = Some(amount.min(up_to));
     }
 }
+
+#[test]
+fn test_trace() {
+    use crate::modifier::Type;
+    use crate::stackvec;
+
+    let player = Build::new_player();
+    let mods = vec![
+        Mod { stat: StatId::Strength, typ: Type::Base, amount: 50, ..Default::default() },
+        Mod { stat: StatId::MaximumLife, typ: Type::Base, amount: 1, mutations: stackvec![Mutation::MultiplierStat((10, StatId::Strength))], ..Default::default() },
+        Mod { stat: StatId::MaximumLife, typ: Type::Base, amount: 100, conditions: stackvec![Condition::PropertyBool((true, property::Bool::OnLowLife))], ..Default::default() },
+    ];
+
+    let mut evaluator = Evaluator::new(&player, &mods, BitFlags::EMPTY, BitFlags::EMPTY).with_trace();
+    let life = evaluator.get_stat_val(StatId::MaximumLife);
+    assert_eq!(life, 5);
+    let trace = evaluator.trace_tree(StatId::MaximumLife).unwrap();
+    assert_eq!(trace.mods.len(), 2);
+    let per_strength = trace.mods.iter().find(|t| !t.m.mutations.is_empty()).unwrap();
+    assert_eq!(per_strength.amount_after, Some(life));
+    assert_eq!(per_strength.dependencies.len(), 1);
+    assert_eq!(per_strength.dependencies[0].stat, StatId::Strength);
+    // The low life mod is recorded as skipped
+    let low_life = trace.mods.iter().find(|t| !t.conditions.is_empty()).unwrap();
+    assert!(!low_life.conditions[0].1);
+    assert_eq!(low_life.amount_after, None);
+
+    // Nothing is recorded without tracing
+    let mut evaluator = Evaluator::new(&player, &mods, BitFlags::EMPTY, BitFlags::EMPTY);
+    assert_eq!(evaluator.get_stat_val(StatId::MaximumLife), life);
+    assert!(evaluator.trace_tree(StatId::MaximumLife).is_none());
+}
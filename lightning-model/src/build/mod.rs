@@ -1,21 +1,26 @@
 pub mod property;
 pub mod stat;
 pub mod evaluator;
+pub mod enemy;
 
 use std::rc::Rc;
 use std::sync::Arc;
 use std::{fs, io};
 use std::path::Path;
 
-use crate::build::evaluator::Evaluator;
+use crate::build::enemy::{EnemyConfig, EnemyValue};
+use crate::build::evaluator::{Evaluator, StatTrace};
 use crate::data::base_item::ItemClass;
-use crate::data::gem::{ActiveSkillType, GemTag};
-use crate::data::{MONSTER_STATS, TREE};
+use crate::data::gem::{ActiveSkillType, GemTag, MinionData};
+use crate::data::{GEMS, ITEMS, MONSTER_STATS, TREE};
+use crate::diagnostics::{DataKind, Diagnostic, Diagnostics};
 use crate::gem::Gem;
 use crate::item::Item;
-use crate::modifier::{Condition, Mod, ModFlag, Mutation, Source, Type};
+use crate::modifier::{parse_mod, Condition, Mod, ModFlag, Mutation, Source, Type};
 use crate::stackvec;
 use crate::tree::PassiveTree;
+use arc_swap::ArcSwapOption;
+use derivative::Derivative;
 use enumflags2::BitFlags;
 use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
@@ -25,6 +30,18 @@ use stat::{Stat, StatId, Stats};
 use strum::EnumCount;
 use strum_macros::{AsRefStr, EnumIter};
 
+pub struct Warcry {
+    /// Attacks exerted by each use of the warcry
+    pub exerted_attacks: i64,
+    /// More damage dealt by exerted attacks
+    pub exert_more_damage: i64,
+    pub duration: i64,
+    /// Applied to the player while the warcry is active
+    pub buffs: Vec<Mod>,
+    /// Applied to enemies while the warcry is active
+    pub debuffs: Vec<Mod>,
+}
+
 #[derive(Serialize, Deserialize, Default, Eq, PartialEq, Hash, Clone, Copy, Debug, strum_macros::Display)]
 pub enum Slot {
     #[default]
@@ -89,6 +106,26 @@ impl TryFrom<(&str, u16)> for Slot {
     }
 }
 
+/// Subjects map mod texts start with, and whether the mod applies to the enemy.
+/// Texts without a known subject apply to the player
+const MAP_MOD_SUBJECTS: &[(&str, bool)] = &[
+    ("monsters have ", true),
+    ("monsters deal ", true),
+    ("monsters ", true),
+    ("players have ", false),
+    ("players deal ", false),
+    ("players ", false),
+];
+
+/// Parses a map mod like "monsters have 40% more life", returns its mods and whether they apply to the enemy
+pub fn parse_map_mod(text: &str) -> Option<(Vec<Mod>, bool)> {
+    let lowercase = text.trim().to_lowercase();
+    let (text, enemy) = MAP_MOD_SUBJECTS.iter()
+        .find_map(|(subject, enemy)| lowercase.strip_prefix(subject).map(|rest| (rest, *enemy)))
+        .unwrap_or((lowercase.as_str(), false));
+    parse_mod(text, Source::Custom("Map")).map(|mods| (mods, enemy))
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum Defence {
     Armour,
@@ -130,6 +167,42 @@ pub enum CampaignChoice {
     ActTen,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, AsRefStr, EnumIter)]
+pub enum PantheonMajor {
+    #[default]
+    None,
+    #[strum(serialize = "Soul of the Brine King")]
+    BrineKing,
+    #[strum(serialize = "Soul of Arakaali")]
+    Arakaali,
+    #[strum(serialize = "Soul of Solaris")]
+    Solaris,
+    #[strum(serialize = "Soul of Lunaris")]
+    Lunaris,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, AsRefStr, EnumIter)]
+pub enum PantheonMinor {
+    #[default]
+    None,
+    #[strum(serialize = "Soul of Abberath")]
+    Abberath,
+    #[strum(serialize = "Soul of Gruthkul")]
+    Gruthkul,
+    #[strum(serialize = "Soul of Yugul")]
+    Yugul,
+    #[strum(serialize = "Soul of Shakari")]
+    Shakari,
+    #[strum(serialize = "Soul of Tukohama")]
+    Tukohama,
+    #[strum(serialize = "Soul of Ralakesh")]
+    Ralakesh,
+    #[strum(serialize = "Soul of Garukhan")]
+    Garukhan,
+    #[strum(serialize = "Soul of Ryslatha")]
+    Ryslatha,
+}
+
 lazy_static! {
     pub static ref BANDIT_STATS: FxHashMap<BanditChoice, Vec<Mod>> = {
         let mut ret = FxHashMap::default();
@@ -238,6 +311,398 @@ lazy_static! {
         ret
     };
 
+    /// Pantheon powers by god, the first entry is the base power followed by each upgrade.
+    /// Only effects expressible with the current stats are listed, effects scaling with
+    /// nearby enemies, hits taken or time stationary assume their maximum and those
+    /// requiring you to be moving assume you are.
+    /// Powers left empty have no modelled effect and are reported by `Build::diagnostics`
+    pub static ref PANTHEON_MAJOR_STATS: FxHashMap<PantheonMajor, Vec<Vec<Mod>>> = {
+        let mut ret = FxHashMap::default();
+        ret.insert(PantheonMajor::None, vec![
+            vec![],
+        ]);
+        ret.insert(PantheonMajor::BrineKing, vec![
+            vec![],
+            vec![
+                Mod {
+                    stat: StatId::StunThreshold,
+                    typ: Type::Inc,
+                    amount: 30,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+            vec![],
+        ]);
+        ret.insert(PantheonMajor::Arakaali, vec![
+            vec![
+                Mod {
+                    stat: StatId::DamageOverTimeTaken,
+                    typ: Type::Inc,
+                    amount: -10,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+            vec![],
+            vec![],
+        ]);
+        ret.insert(PantheonMajor::Solaris, vec![
+            vec![
+                Mod {
+                    stat: StatId::PhysicalDamageReduction,
+                    typ: Type::Base,
+                    amount: 6,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+            vec![],
+            vec![],
+        ]);
+        ret.insert(PantheonMajor::Lunaris, vec![
+            vec![
+                Mod {
+                    stat: StatId::PhysicalDamageReduction,
+                    typ: Type::Base,
+                    amount: 8,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+                Mod {
+                    stat: StatId::MovementSpeed,
+                    typ: Type::Inc,
+                    amount: 8,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+            vec![],
+            vec![],
+        ]);
+        ret
+    };
+
+    pub static ref PANTHEON_MINOR_STATS: FxHashMap<PantheonMinor, Vec<Vec<Mod>>> = {
+        let mut ret = FxHashMap::default();
+        ret.insert(PantheonMinor::None, vec![
+            vec![],
+        ]);
+        ret.insert(PantheonMinor::Abberath, vec![
+            vec![
+                Mod {
+                    stat: StatId::IgniteDurationOnSelf,
+                    typ: Type::More,
+                    amount: -60,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+            vec![],
+        ]);
+        ret.insert(PantheonMinor::Gruthkul, vec![
+            vec![
+                Mod {
+                    stat: StatId::PhysicalDamageReduction,
+                    typ: Type::Base,
+                    amount: 5,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+            vec![],
+        ]);
+        ret.insert(PantheonMinor::Yugul, vec![
+            vec![
+                Mod {
+                    stat: StatId::ReflectedDamageTaken,
+                    typ: Type::Inc,
+                    amount: -50,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+            vec![],
+        ]);
+        ret.insert(PantheonMinor::Shakari, vec![
+            vec![
+                Mod {
+                    stat: StatId::PoisonDurationOnSelf,
+                    typ: Type::More,
+                    amount: -50,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+            vec![],
+        ]);
+        ret.insert(PantheonMinor::Tukohama, vec![
+            vec![
+                Mod {
+                    stat: StatId::PhysicalDamageReduction,
+                    typ: Type::Base,
+                    amount: 8,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+            vec![
+                Mod {
+                    stat: StatId::LifeRegenerationPct,
+                    typ: Type::Base,
+                    amount: 200,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+        ]);
+        ret.insert(PantheonMinor::Ralakesh, vec![
+            vec![
+                Mod {
+                    stat: StatId::PhysicalDamageOverTimeTaken,
+                    typ: Type::Inc,
+                    amount: -25,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+            vec![],
+        ]);
+        ret.insert(PantheonMinor::Garukhan, vec![
+            vec![],
+            vec![
+                Mod {
+                    stat: StatId::ChanceToEvade,
+                    typ: Type::Base,
+                    amount: 5,
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+        ]);
+        ret.insert(PantheonMinor::Ryslatha, vec![
+            vec![],
+            vec![
+                Mod {
+                    stat: StatId::FlaskRecoveryAmount,
+                    typ: Type::Inc,
+                    amount: 60,
+                    conditions: stackvec![Condition::PropertyBool((true, property::Bool::OnLowLife))],
+                    source: Source::Custom("Pantheon"),
+                    ..Default::default()
+                },
+            ],
+        ]);
+        ret
+    };
+
+    /// Warcries are keyed by gem name, duration is used when the gem has no `base_skill_effect_duration`
+    pub static ref WARCRY_STATS: FxHashMap<&'static str, Warcry> = {
+        let mut ret = FxHashMap::default();
+        ret.insert("Intimidating Cry", Warcry {
+            exerted_attacks: 3,
+            exert_more_damage: 100,
+            duration: 4000,
+            buffs: vec![],
+            debuffs: vec![
+                Mod {
+                    stat: StatId::DamageTaken,
+                    typ: Type::Inc,
+                    amount: 10,
+                    tags: GemTag::Attack.into(),
+                    source: Source::Custom("Intimidate"),
+                    ..Default::default()
+                },
+            ],
+        });
+        ret.insert("Seismic Cry", Warcry {
+            exerted_attacks: 3,
+            exert_more_damage: 25,
+            duration: 4000,
+            buffs: vec![],
+            debuffs: vec![],
+        });
+        ret.insert("Ancestral Cry", Warcry {
+            exerted_attacks: 5,
+            exert_more_damage: 0,
+            duration: 8000,
+            buffs: vec![
+                Mod {
+                    stat: StatId::FireResistance,
+                    typ: Type::Base,
+                    amount: 10,
+                    source: Source::Custom("Ancestral Cry"),
+                    ..Default::default()
+                },
+                Mod {
+                    stat: StatId::ColdResistance,
+                    typ: Type::Base,
+                    amount: 10,
+                    source: Source::Custom("Ancestral Cry"),
+                    ..Default::default()
+                },
+                Mod {
+                    stat: StatId::LightningResistance,
+                    typ: Type::Base,
+                    amount: 10,
+                    source: Source::Custom("Ancestral Cry"),
+                    ..Default::default()
+                },
+            ],
+            debuffs: vec![],
+        });
+        ret.insert("Battlemage's Cry", Warcry {
+            exerted_attacks: 0,
+            exert_more_damage: 0,
+            duration: 8000,
+            buffs: vec![
+                Mod {
+                    stat: StatId::CriticalStrikeChance,
+                    typ: Type::Inc,
+                    amount: 50,
+                    source: Source::Custom("Battlemage's Cry"),
+                    ..Default::default()
+                },
+            ],
+            debuffs: vec![],
+        });
+        ret
+    };
+
+    /// Temporary buffs on the player, each gated by its config toggle
+    static ref BUFF_MODES: Vec<Mod> = vec![
+        Mod {
+            stat: StatId::AttackSpeed,
+            typ: Type::Inc,
+            amount: 20,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::Onslaught))],
+            source: Source::Custom("Onslaught"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::CastSpeed,
+            typ: Type::Inc,
+            amount: 20,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::Onslaught))],
+            source: Source::Custom("Onslaught"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::MovementSpeed,
+            typ: Type::Inc,
+            amount: 20,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::Onslaught))],
+            source: Source::Custom("Onslaught"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::Damage,
+            typ: Type::More,
+            amount: 10,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::ArcaneSurge))],
+            tags: GemTag::Spell.into(),
+            source: Source::Custom("Arcane Surge"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::CastSpeed,
+            typ: Type::Inc,
+            amount: 10,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::ArcaneSurge))],
+            source: Source::Custom("Arcane Surge"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::ManaRegenerationRate,
+            typ: Type::Inc,
+            amount: 30,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::ArcaneSurge))],
+            source: Source::Custom("Arcane Surge"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::MovementSpeed,
+            typ: Type::Inc,
+            amount: 30,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::Elusive))],
+            source: Source::Custom("Elusive"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::ChanceToAvoidDamageFromHits,
+            typ: Type::Base,
+            amount: 15,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::Elusive))],
+            source: Source::Custom("Elusive"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::ActionSpeed,
+            typ: Type::Inc,
+            amount: 8,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::Tailwind))],
+            source: Source::Custom("Tailwind"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::PhysicalGainedAsChaos,
+            typ: Type::Base,
+            amount: 30,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::UnholyMight))],
+            source: Source::Custom("Unholy Might"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::Damage,
+            typ: Type::Inc,
+            amount: 100,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::Adrenaline))],
+            source: Source::Custom("Adrenaline"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::AttackSpeed,
+            typ: Type::Inc,
+            amount: 25,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::Adrenaline))],
+            source: Source::Custom("Adrenaline"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::CastSpeed,
+            typ: Type::Inc,
+            amount: 25,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::Adrenaline))],
+            source: Source::Custom("Adrenaline"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::MovementSpeed,
+            typ: Type::Inc,
+            amount: 25,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::Adrenaline))],
+            source: Source::Custom("Adrenaline"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::PhysicalDamageReduction,
+            typ: Type::Base,
+            amount: 10,
+            conditions: stackvec![Condition::PropertyBool((true, property::Bool::Adrenaline))],
+            source: Source::Custom("Adrenaline"),
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::DamageTaken,
+            typ: Type::More,
+            amount: -1,
+            mutations: stackvec![Mutation::MultiplierProperty((1, property::Int::Fortification))],
+            flags: ModFlag::Hit.into(),
+            source: Source::Custom("Fortification"),
+            ..Default::default()
+        },
+    ];
+
     static ref BASE_MODES: Vec<Mod> = vec![
         Mod {
             stat: StatId::MaximumLife,
@@ -386,6 +851,66 @@ lazy_static! {
             ],
             ..Default::default()
         },
+        Mod {
+            stat: StatId::PhysicalDamageReduction,
+            typ: Type::Base,
+            amount: 4,
+            mutations: stackvec![
+                Mutation::MultiplierProperty((1, property::Int::EnduranceCharges)),
+            ],
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::FireResistance,
+            typ: Type::Base,
+            amount: 4,
+            mutations: stackvec![
+                Mutation::MultiplierProperty((1, property::Int::EnduranceCharges)),
+            ],
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::ColdResistance,
+            typ: Type::Base,
+            amount: 4,
+            mutations: stackvec![
+                Mutation::MultiplierProperty((1, property::Int::EnduranceCharges)),
+            ],
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::LightningResistance,
+            typ: Type::Base,
+            amount: 4,
+            mutations: stackvec![
+                Mutation::MultiplierProperty((1, property::Int::EnduranceCharges)),
+            ],
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::SuppressedSpellDamagePrevented,
+            typ: Type::Base,
+            amount: 50,
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::CurseLimit,
+            typ: Type::Base,
+            amount: 1,
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::MaximumChanceToBlockAttackDamage,
+            typ: Type::Base,
+            amount: 75,
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::MaximumChanceToBlockSpellDamage,
+            typ: Type::Base,
+            amount: 75,
+            ..Default::default()
+        },
         Mod {
             stat: StatId::MaximumFireResistance,
             typ: Type::Base,
@@ -442,11 +967,54 @@ lazy_static! {
             amount: 20,
             ..Default::default()
         },
+        Mod {
+            stat: StatId::MaximumImpales,
+            typ: Type::Base,
+            amount: 5,
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::MaximumTotems,
+            typ: Type::Base,
+            amount: 1,
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::MaximumTraps,
+            typ: Type::Base,
+            amount: 15,
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::MaximumMines,
+            typ: Type::Base,
+            amount: 15,
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::MaximumLifeLeechRate,
+            typ: Type::Base,
+            amount: 20,
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::MaximumManaLeechRate,
+            typ: Type::Base,
+            amount: 20,
+            ..Default::default()
+        },
+        Mod {
+            stat: StatId::MaximumEnergyShieldLeechRate,
+            typ: Type::Base,
+            amount: 10,
+            ..Default::default()
+        },
     ];
 }
 
 #[serde_as]
-#[derive(Default, Clone, Serialize, Deserialize)]
+#[derive(Derivative, Default, Serialize, Deserialize)]
+#[derivative(Clone)]
 pub struct Build {
     pub name: String,
     pub gem_links: Vec<GemLink>,
@@ -459,13 +1027,37 @@ pub struct Build {
     pub bandit_choice: BanditChoice,
     #[serde(default)]
     pub campaign_choice: CampaignChoice,
+    #[serde(default)]
+    pub pantheon_major: PantheonMajor,
+    /// Number of unlocked upgrades of the major god
+    #[serde(default)]
+    pub pantheon_major_upgrades: usize,
+    #[serde(default)]
+    pub pantheon_minor: PantheonMinor,
+    #[serde(default)]
+    pub pantheon_minor_upgrades: usize,
+    #[serde(default)]
+    pub enemy: EnemyConfig,
+    /// Map affixes, one mod per entry
+    #[serde(default)]
+    map_mods: Vec<String>,
+    /// Parsed `map_mods` and whether they apply to the enemy, None until parsed after a change
+    #[serde(skip)]
+    #[derivative(Clone(clone_with = "clone_arc_swap_option"))]
+    map_mod_cache: ArcSwapOption<Vec<(Mod, bool)>>,
     properties_int: FxHashMap<property::Int, i64>,
     properties_bool: FxHashMap<property::Bool, bool>,
     #[serde(default)]
     properties_always_max: FxHashSet<property::Int>,
+    #[serde(default)]
+    active_flasks: FxHashSet<u16>,
     pub import_account: Option<(String, String)>,
 }
 
+fn clone_arc_swap_option<T>(cache: &ArcSwapOption<T>) -> ArcSwapOption<T> {
+    ArcSwapOption::new(cache.load_full())
+}
+
 impl Build {
     pub fn new_player() -> Build {
         let mut ret = Build {
@@ -480,7 +1072,7 @@ impl Build {
         self.tree.nodes_additional.clear();
         self.tree.invalidate_modcache();
         let mut max_abyssal_sockets = 0;
-        let equipment_slots: Vec<(Slot, usize)> = self.equipment.iter().map(|(k, v)| (*k, *v)).collect();
+        let equipment_slots: Vec<(Slot, usize)> = self.equipment.iter().filter(|(_, v)| self.inventory[**v].has_data()).map(|(k, v)| (*k, *v)).collect();
         for (slot, idx) in equipment_slots {
             if matches!(slot, Slot::AbyssalJewel(_)) {
                 continue;
@@ -528,12 +1120,67 @@ impl Build {
         ret
     }
 
+    /// Returns the mods applied to the enemy by enabled curse and mark gems, each scaled by the
+    /// curse effect of `player_mods`, the gem and the supports of its link.
+    /// Only the first curses up to the curse limit apply, and a single mark.
+    pub fn calc_curses(&self, player_mods: &[Mod]) -> Vec<Mod> {
+        let mut best_gems: Vec<(&Gem, &GemLink)> = vec![];
+        for link in &self.gem_links {
+            for active_gem in link.active_gems().filter(|gem| gem.enabled && (gem.data().tags.contains(&GemTag::Curse) || gem.data().tags.contains(&GemTag::Mark))) {
+                match best_gems.iter_mut().find(|(gem, _)| gem.id == active_gem.id) {
+                    Some(existing) if existing.0.level < active_gem.level => *existing = (active_gem, link),
+                    Some(_) => (),
+                    None => best_gems.push((active_gem, link)),
+                }
+            }
+        }
+
+        let curse_limit = self.calc_stats(player_mods, BitFlags::EMPTY, BitFlags::EMPTY).val(StatId::CurseLimit);
+        let (marks, curses): (Vec<_>, Vec<_>) = best_gems.into_iter().partition(|(gem, _)| gem.data().tags.contains(&GemTag::Mark));
+
+        let mut ret = vec![];
+        for (gem, link) in curses.into_iter().take(curse_limit.max(0) as usize).chain(marks.into_iter().take(1)) {
+            let mut mods = player_mods.to_vec();
+            let gem_mods = gem.calc_mods(false);
+            mods.extend_from_slice(&gem_mods);
+            for support_gem in link.support_gems().filter(|g| g.enabled && g.can_support(gem)) {
+                mods.extend_from_slice(&support_gem.calc_mods(false));
+            }
+            let tags = gem.data().tags.iter().copied().map(BitFlags::from).fold(BitFlags::empty(), |acc, flag| acc | flag);
+            let curse_mult = Evaluator::new(self, &mods, tags, BitFlags::EMPTY).get_stat_mult(StatId::CurseEffect);
+            ret.extend(gem_mods.iter().filter(|m| m.flags.contains(ModFlag::Curse)).map(|m| Mod {
+                revised_amount: Some((m.final_amount() * curse_mult) / 10000),
+                ..*m
+            }));
+        }
+        ret
+    }
+
+    /// Enabled warcry gems with known effects, the highest level one of each
+    pub fn calc_warcries(&self) -> Vec<&Gem> {
+        let mut best_gems: FxHashMap<&str, &Gem> = FxHashMap::default();
+        for link in &self.gem_links {
+            for active_gem in link.active_gems().filter(|gem| gem.enabled && WARCRY_STATS.contains_key(gem.data().base_item.display_name.as_str())) {
+                if let Some(existing_gem) = best_gems.get(active_gem.id.as_str()) {
+                    if existing_gem.level >= active_gem.level {
+                        continue;
+                    }
+                }
+                best_gems.insert(active_gem.id.as_str(), active_gem);
+            }
+        }
+        let mut ret: Vec<&Gem> = best_gems.into_values().collect();
+        ret.sort_by_key(|gem| gem.id.as_str());
+        ret
+    }
+
     /// Returns mods from the following sources:
     /// Innate, Passive Tree, Items, Global Skills (Auras..)
     pub fn calc_mods(&self, include_global: bool) -> Vec<Mod> {
         let class_data = &TREE.classes[&self.tree.class];
         let mut mods = Vec::with_capacity(600);
         mods.extend_from_slice(&BASE_MODES);
+        mods.extend_from_slice(&BUFF_MODES);
         mods.extend_from_slice(&[
             Mod {
                 stat: StatId::Strength,
@@ -556,7 +1203,12 @@ impl Build {
         ]);
         mods.append(&mut BANDIT_STATS.get(&self.bandit_choice).unwrap().clone());
         mods.append(&mut CAMPAIGN_STATS.get(&self.campaign_choice).unwrap().clone());
-        let jewels: FxHashMap<u32, Arc<Item>> = self.equipment.iter().filter_map(|(k, v)| {
+        mods.extend(PANTHEON_MAJOR_STATS[&self.pantheon_major].iter().take(self.pantheon_major_upgrades + 1).flatten());
+        mods.extend(PANTHEON_MINOR_STATS[&self.pantheon_minor].iter().take(self.pantheon_minor_upgrades + 1).flatten());
+        mods.extend(self.calc_map_mods(false));
+        // Items missing from the game data are kept in the build but not evaluated
+        let equipment = self.equipment.iter().filter(|(_, idx)| self.inventory[**idx].has_data());
+        let jewels: FxHashMap<u32, Arc<Item>> = equipment.clone().filter_map(|(k, v)| {
             if let Slot::TreeJewel(id) = k {
                 Some((*id, self.inventory[*v].clone()))
             } else {
@@ -564,7 +1216,7 @@ impl Build {
             }
         }).collect();
         mods.extend_from_slice(&self.tree.calc_mods(&jewels));
-        for (slot, idx) in &self.equipment {
+        for (slot, idx) in equipment {
             let item = &self.inventory[*idx];
             if let Slot::TreeJewel(node_id) = slot {
                 if self.tree.nodes.contains(node_id) {
@@ -574,6 +1226,13 @@ impl Build {
                         mods.push(new_mod);
                     }
                 }
+            } else if let Slot::Flask(flask_idx) = slot {
+                if self.is_flask_active(*flask_idx) {
+                    for mut new_mod in item.calc_flask_mods() {
+                        new_mod.source = Source::Item(*slot);
+                        mods.push(new_mod);
+                    }
+                }
             } else {
                 for m in item.calc_nonlocal_mods().iter() {
                     let mut new_mod = m.to_owned();
@@ -601,31 +1260,180 @@ impl Build {
         mods
     }
 
-    pub fn calc_mods_monster(level: i64) -> Vec<Mod> {
+    pub fn map_mods(&self) -> &[String] {
+        &self.map_mods
+    }
+
+    /// Map mods for editing, they are parsed again on the next `calc_map_mods`
+    pub fn map_mods_mut(&mut self) -> &mut Vec<String> {
+        self.map_mod_cache.store(None);
+        &mut self.map_mods
+    }
+
+    /// Parsed mods of the configured map mods applying to the enemy, or else to the player
+    pub fn calc_map_mods(&self, enemy: bool) -> Vec<Mod> {
+        let cache = match self.map_mod_cache.load_full() {
+            Some(cache) => cache,
+            None => {
+                let parsed: Vec<(Mod, bool)> = self.map_mods.iter()
+                    .filter_map(|text| parse_map_mod(text))
+                    .flat_map(|(mods, is_enemy)| mods.into_iter().map(move |m| (m, is_enemy)))
+                    .collect();
+                let cache = Arc::new(parsed);
+                self.map_mod_cache.store(Some(cache.clone()));
+                cache
+            }
+        };
+        cache.iter().filter(|(_, is_enemy)| *is_enemy == enemy).map(|(m, _)| *m).collect()
+    }
+
+    /// Parts of the build the calculations ignore or estimate: unknown gems and items, minion gems without
+    /// monster data, reserving gems without reservation data for their level, unsupported item and map mods
+    pub fn diagnostics(&self) -> Diagnostics {
+        let mut diagnostics = Diagnostics::default();
+        for gem in self.gem_links.iter().flat_map(|link| &link.gems).filter(|gem| !gem.has_data()) {
+            diagnostics.push(Diagnostic::MissingData { kind: DataKind::Gem, name: gem.id.clone() });
+        }
+        for item in self.inventory.iter().filter(|item| !item.has_data()) {
+            diagnostics.push(Diagnostic::MissingData { kind: DataKind::BaseItem, name: item.base_item.clone() });
+        }
+        for gem in self.gem_links.iter().flat_map(|link| link.active_gems()).filter(|gem| gem.enabled && gem.has_data()) {
+            let creates_minion = gem.data().active_skill.as_ref().is_some_and(|s| s.types.contains(&ActiveSkillType::CreatesMinion));
+            if creates_minion && gem.data().minion.is_none() {
+                diagnostics.push(Diagnostic::IncompleteData { kind: DataKind::Minion, name: gem.data().display_name().to_string() });
+            }
+            if gem.has_reservation() && gem.reservations_level().is_none() {
+                diagnostics.push(Diagnostic::IncompleteData { kind: DataKind::Reservation, name: format!("{} level {}", gem.data().display_name(), gem.level) });
+            }
+        }
+        for (slot, idx) in self.equipment.iter().filter(|(_, idx)| self.inventory[**idx].has_data()) {
+            for text in self.inventory[*idx].unparsed_mods() {
+                // Triggers granted by items are handled by the skill calculations when supported
+                match crate::calc::item_mod_trigger(text) {
+                    Some(Some(_)) => (),
+                    Some(None) => diagnostics.push(Diagnostic::UnmodelledEffect { name: format!("trigger \"{text}\" on {slot}") }),
+                    None => diagnostics.push(Diagnostic::UnparsedMod { text: text.to_owned(), source: Source::Item(*slot) }),
+                }
+            }
+        }
+        let pantheon = [
+            (self.pantheon_major != PantheonMajor::None, self.pantheon_major.as_ref(), &PANTHEON_MAJOR_STATS[&self.pantheon_major], self.pantheon_major_upgrades),
+            (self.pantheon_minor != PantheonMinor::None, self.pantheon_minor.as_ref(), &PANTHEON_MINOR_STATS[&self.pantheon_minor], self.pantheon_minor_upgrades),
+        ];
+        for (_, god, tiers, upgrades) in pantheon.into_iter().filter(|(chosen, ..)| *chosen) {
+            for (upgrade, _) in tiers.iter().enumerate().take(upgrades + 1).filter(|(_, tier)| tier.is_empty()) {
+                let name = match upgrade {
+                    0 => god.to_string(),
+                    _ => format!("{god} upgrade {upgrade}"),
+                };
+                diagnostics.push(Diagnostic::UnmodelledEffect { name });
+            }
+        }
+        for text in self.map_mods.iter().filter(|text| parse_map_mod(text).is_none()) {
+            diagnostics.push(Diagnostic::UnparsedMod { text: text.to_owned(), source: Source::Custom("Map") });
+        }
+        diagnostics
+    }
+
+    pub fn calc_mods_monster(level: i64, enemy: &EnemyConfig) -> Vec<Mod> {
         let default_stats = MONSTER_STATS.get(&level).unwrap();
-        let mods = vec![
+        let mut mods = vec![
             Mod {
                 stat: StatId::MaximumLife,
                 typ: Type::Base,
                 amount: default_stats.life,
                 ..Default::default()
             },
+            Mod {
+                stat: StatId::AilmentThreshold,
+                typ: Type::Base,
+                amount: (default_stats.life * enemy.value(EnemyValue::AilmentThresholdMultiplier)) / 100,
+                ..Default::default()
+            },
             Mod {
                 stat: StatId::EvasionRating,
                 typ: Type::Base,
-                amount: default_stats.evasion,
+                amount: (default_stats.evasion * enemy.value(EnemyValue::EvasionMultiplier)) / 100,
                 ..Default::default()
             },
             Mod {
                 stat: StatId::Armour,
                 typ: Type::Base,
-                amount: default_stats.armour,
+                amount: (default_stats.armour * enemy.value(EnemyValue::ArmourMultiplier)) / 100,
+                ..Default::default()
+            },
+            Mod {
+                stat: StatId::CurseEffectOnSelf,
+                typ: Type::More,
+                amount: -enemy.value(EnemyValue::CurseEffectReduction),
+                source: Source::Custom("Enemy"),
+                ..Default::default()
+            },
+            Mod {
+                stat: StatId::DamageTaken,
+                typ: Type::More,
+                amount: -enemy.value(EnemyValue::LessDamageTaken),
+                source: Source::Custom("Enemy"),
                 ..Default::default()
             },
         ];
+        for (stat, value) in [
+            (StatId::FireResistance, EnemyValue::FireResistance),
+            (StatId::ColdResistance, EnemyValue::ColdResistance),
+            (StatId::LightningResistance, EnemyValue::LightningResistance),
+            (StatId::ChaosResistance, EnemyValue::ChaosResistance),
+        ] {
+            mods.push(Mod {
+                stat,
+                typ: Type::Base,
+                amount: enemy.value(value),
+                ..Default::default()
+            });
+        }
         mods
     }
 
+    /// Returns the mods of a minion of the given level: its base stats scaled by the
+    /// monster of the minion gem, plus the "minions have/deal" mods among `mods` with their minion tag removed.
+    pub fn calc_mods_minion(level: i64, minion: &MinionData, mods: &[Mod]) -> Vec<Mod> {
+        let ally_life = MONSTER_STATS.get(&level).map_or(0, |m| m.ally_life);
+        let accuracy = MONSTER_STATS.get(&level).map_or(0, |m| m.accuracy);
+        let mut ret = vec![
+            Mod {
+                stat: StatId::MaximumLife,
+                typ: Type::Base,
+                amount: (ally_life * minion.life_multiplier) / 100,
+                ..Default::default()
+            },
+            Mod {
+                stat: StatId::AccuracyRating,
+                typ: Type::Base,
+                amount: accuracy,
+                ..Default::default()
+            },
+            Mod {
+                stat: StatId::CriticalStrikeMultiplier,
+                typ: Type::Base,
+                amount: 150,
+                ..Default::default()
+            },
+        ];
+        for stat in [StatId::MaximumFireResistance, StatId::MaximumColdResistance, StatId::MaximumLightningResistance, StatId::MaximumChaosResistance] {
+            ret.push(Mod {
+                stat,
+                typ: Type::Base,
+                amount: 75,
+                ..Default::default()
+            });
+        }
+        ret.extend(mods.iter().filter(|m| m.tags.contains(GemTag::Minion)).map(|m| {
+            let mut m = m.to_owned();
+            m.tags.remove(GemTag::Minion);
+            m
+        }));
+        ret
+    }
+
     pub fn remove_inventory(&mut self, idx_remove: usize) {
         if idx_remove >= self.inventory.len() {
             eprintln!("Trying to remove inventory item {idx_remove} but len is {}", self.inventory.len());
@@ -739,7 +1547,7 @@ impl Build {
                 property::Val::Stat(_) => i64::MAX
             }
         };
-        self.properties_int.get(&p).copied().unwrap_or(0).clamp(min, max)
+        self.properties_int.get(&p).copied().unwrap_or(property::int_data(p).default).clamp(min, max)
     }
 
     pub fn property_int_stats(&self, p: property::Int, stats: &Stats) -> i64 {
@@ -770,12 +1578,34 @@ impl Build {
         self.properties_bool.insert(p, val);
     }
 
+    pub fn is_flask_active(&self, flask_idx: u16) -> bool {
+        self.active_flasks.contains(&flask_idx)
+    }
+
+    pub fn set_flask_active(&mut self, flask_idx: u16, active: bool) {
+        if active {
+            self.active_flasks.insert(flask_idx);
+        } else {
+            self.active_flasks.remove(&flask_idx);
+        }
+    }
+
+    /// Whether an equipped flask is toggled active
+    pub fn has_active_flask(&self) -> bool {
+        self.equipment.keys().any(|slot| matches!(slot, Slot::Flask(idx) if self.is_flask_active(*idx)))
+    }
+
     pub fn is_holding(&self, item_classes: &BitFlags<ItemClass>) -> bool {
         self.equipment.iter().find(|(_, idx)| item_classes.contains(self.inventory[**idx].data().item_class)).is_some()
     }
 
     pub fn calc_stats(&self, mods: &[Mod], tags: BitFlags<GemTag>, flags: BitFlags<ModFlag>) -> Stats {
-        let mut evaluator = Evaluator::new(self, mods, tags, flags);
+        self.calc_stats_leeching(mods, tags, flags, false)
+    }
+
+    /// Like `calc_stats`, with the mods applying while leeching enabled or not
+    pub fn calc_stats_leeching(&self, mods: &[Mod], tags: BitFlags<GemTag>, flags: BitFlags<ModFlag>, leeching: bool) -> Stats {
+        let mut evaluator = Evaluator::new(self, mods, tags, flags).with_leeching(leeching);
 
         let stat_ids: Vec<StatId> = evaluator.mods_by_stat.keys().copied().collect();
 
@@ -783,7 +1613,7 @@ impl Build {
             evaluator.eval_stat(stat_id);
         }
 
-        Stats { stats: evaluator.resolved_stats }
+        Stats { stats: evaluator.resolved_stats, diagnostics: evaluator.diagnostics }
     }
 
     pub fn calc_stat(&self, stat_id: StatId, mods: &[Mod], tags: BitFlags<GemTag>, flags: BitFlags<ModFlag>) -> Stat {
@@ -792,6 +1622,14 @@ impl Build {
         evaluator.eval_stat(stat_id).clone()
     }
 
+    /// Like `calc_stat`, returning how the stat was derived instead
+    pub fn trace_stat(&self, stat_id: StatId, mods: &[Mod], tags: BitFlags<GemTag>, flags: BitFlags<ModFlag>, leeching: bool) -> StatTrace {
+        let mut evaluator = Evaluator::new(self, mods, tags, flags).with_leeching(leeching).with_trace();
+
+        evaluator.eval_stat(stat_id);
+        evaluator.trace_tree(stat_id).unwrap()
+    }
+
     pub fn save(&self, dir: &Path) -> io::Result<()> {
         let mut file_path = dir.join(&self.name);
         file_path.set_extension("json");
@@ -807,3 +1645,117 @@ fn test_build() {
 
     assert_eq!(stats.stat(StatId::MaximumLife).val(), 60);
 }
+
+#[test]
+fn test_diagnostics() {
+    let mut player = Build::new_player();
+    let mods = vec![
+        Mod { stat: StatId::Strength, typ: Type::Base, amount: 10, mutations: stackvec![Mutation::MultiplierStat((1, StatId::Dexterity))], ..Default::default() },
+        Mod { stat: StatId::Dexterity, typ: Type::Base, amount: 10, mutations: stackvec![Mutation::MultiplierStat((1, StatId::Strength))], ..Default::default() },
+    ];
+    let stats = player.calc_stats(&mods, BitFlags::EMPTY, BitFlags::EMPTY);
+    assert!(stats.diagnostics.iter().any(|d| matches!(d, Diagnostic::CircularDependency(path) if path.len() == 3)));
+
+    player.map_mods_mut().push("Monsters are very confused".to_string());
+    assert!(player.diagnostics().iter().any(|d| matches!(d, Diagnostic::UnparsedMod { .. })));
+
+    // Unknown items are reported and skipped, but kept in the build
+    player.inventory.push(Arc::new(Item { base_item: "Removed Ring".to_string(), mods_expl: vec!["+30 to maximum life".to_string()], ..Default::default() }));
+    player.equip(Slot::Ring, 0);
+    assert!(player.diagnostics().iter().any(|d| matches!(d, Diagnostic::MissingData { kind: DataKind::BaseItem, .. })));
+    let stats = player.calc_stats(&player.calc_mods(true), BitFlags::EMPTY, BitFlags::EMPTY);
+    assert_eq!(stats.stat(StatId::MaximumLife).val(), 60);
+    assert_eq!(player.inventory.len(), 1);
+
+    // Item triggers the skill calculations can't follow are reported as such
+    let triggers = vec!["Trigger Level 20 Bone Nova when you Kill an Enemy".to_string(), "Socketed Gems are Supported by Level 20 Cast On Critical Strike".to_string()];
+    player.inventory.push(Arc::new(Item { base_item: "Iron Ring".to_string(), mods_expl: triggers, ..Default::default() }));
+    player.equip(Slot::Ring2, 1);
+    let diagnostics = player.diagnostics();
+    assert!(diagnostics.iter().any(|d| matches!(d, Diagnostic::UnmodelledEffect { name } if name.contains("Bone Nova"))));
+    assert!(!diagnostics.iter().any(|d| matches!(d, Diagnostic::UnparsedMod { source: Source::Item(Slot::Ring2), .. })));
+}
+
+#[test]
+fn test_parse_map_mod() {
+    assert!(parse_map_mod("Monsters have 40% more Life").is_some_and(|(_, enemy)| enemy));
+    assert!(parse_map_mod("Players have -10% to all maximum Resistances").is_some_and(|(_, enemy)| !enemy));
+    assert!(parse_map_mod("Players have 60% less Recovery Rate of Life and Energy Shield").is_some_and(|(_, enemy)| !enemy));
+}
+
+#[test]
+fn test_curses() {
+    let gem = |name: &str| {
+        let id = GEMS.iter().find_map(|(id, data)| (data.display_name() == name).then(|| id.clone())).unwrap();
+        Arc::new(Gem::new(id, true, 20, 0, 0))
+    };
+    let mut player = Build::new_player();
+    player.gem_links.push(GemLink { gems: vec![gem("Vulnerability"), gem("Flammability")], slot: Slot::BodyArmour });
+    let monster_stats = |player_mods: &[Mod]| {
+        let mut monster_mods = Build::calc_mods_monster(83, &player.enemy);
+        monster_mods.extend(player.calc_curses(player_mods));
+        stat::calc_stats(&monster_mods)
+    };
+    let fire_resistance = stat::calc_stats(&Build::calc_mods_monster(83, &player.enemy)).val(StatId::FireResistance);
+
+    // Only the first curse fits the curse limit
+    let mut player_mods = player.calc_mods(true);
+    let stats = monster_stats(&player_mods);
+    assert!(stats.stat(StatId::PhysicalDamageTaken).mult() > 10000);
+    assert_eq!(stats.val(StatId::FireResistance), fire_resistance);
+
+    player_mods.extend(parse_mod("you can apply an additional curse", Source::Innate).unwrap());
+    assert!(monster_stats(&player_mods).val(StatId::FireResistance) < fire_resistance);
+}
+
+#[test]
+fn test_property_default() {
+    let player: Build = serde_json::from_str(&serde_json::to_string(&Build::default()).unwrap()).unwrap();
+    assert_eq!(player.property_int(property::Int::ImpaleStacks), 5);
+    assert_eq!(Build::new_player().property_int(property::Int::ImpaleStacks), 5);
+}
+
+#[test]
+fn test_minion_mods() {
+    let player = Build::new_player();
+    let zombie = MinionData { life_multiplier: 250, damage_multiplier: 100, attack_time: 1150, crit_chance: 500 };
+    let skeleton = MinionData { life_multiplier: 100, damage_multiplier: 100, attack_time: 800, crit_chance: 500 };
+    let mods = parse_mod("minions have 100% increased maximum life", Source::Innate).unwrap();
+    let life = |minion: &MinionData| player.calc_stats(&Build::calc_mods_minion(60, minion, &mods), BitFlags::EMPTY, BitFlags::EMPTY).stat(StatId::MaximumLife).val();
+
+    let ally_life = MONSTER_STATS[&60].ally_life;
+    assert_eq!(life(&skeleton), ally_life * 2);
+    assert_eq!(life(&zombie), (ally_life * 250 / 100) * 2);
+}
+
+#[test]
+fn test_pantheon() {
+    let mut player = Build::new_player();
+    player.pantheon_major = PantheonMajor::Arakaali;
+    player.pantheon_minor = PantheonMinor::Shakari;
+    let stats = player.calc_stats(&player.calc_mods(true), BitFlags::EMPTY, BitFlags::EMPTY);
+    assert_eq!(stats.stat(StatId::DamageOverTimeTaken).val_custom(100), 90);
+    assert_eq!(stats.stat(StatId::PoisonDurationOnSelf).val_custom(100), 50);
+    assert!(player.diagnostics().is_empty());
+
+    // Unmodelled powers are still selectable and reported
+    player.pantheon_major = PantheonMajor::BrineKing;
+    player.pantheon_minor_upgrades = 1;
+    let unmodelled: Vec<String> = player.diagnostics().iter().map(|d| d.to_string()).collect();
+    assert_eq!(unmodelled, ["Effect not modelled: Soul of the Brine King", "Effect not modelled: Soul of Shakari upgrade 1"]);
+}
+
+#[test]
+fn test_map_mods() {
+    let mut player = Build::new_player();
+    player.map_mods_mut().push("Players have -10% to all maximum Resistances".to_string());
+    player.map_mods_mut().push("Monsters have 40% more Life".to_string());
+    assert_eq!(player.calc_map_mods(false).len(), 4);
+    assert_eq!(player.calc_map_mods(true).len(), 1);
+    let stats = player.calc_stats(&player.calc_mods(true), BitFlags::EMPTY, BitFlags::EMPTY);
+    assert_eq!(stats.stat(StatId::MaximumFireResistance).val(), 65);
+
+    // Editing drops the parsed mods
+    player.map_mods_mut().clear();
+    assert!(player.calc_map_mods(false).is_empty());
+}
@@ -11,6 +11,10 @@ pub enum Int {
     EnduranceCharges,
     Fortification,
     Rage,
+    ImpaleStacks,
+    ActiveTotems,
+    ActiveTraps,
+    ActiveMines,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -23,6 +27,8 @@ pub enum Val {
 pub struct IntData {
     pub min: Val,
     pub max: Val,
+    /// Value of a build that never set the property, e.g. one loaded from an older save
+    pub default: i64,
 }
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, Hash, PartialEq, Eq, strum_macros::Display)]
@@ -31,19 +37,29 @@ pub enum Bool {
     Onslaught,
     DealtCritRecently,
     BlockedRecently,
+    /// Leeching regardless of the leech of the skill, e.g. from leech the calculations don't model
     Leeching,
     OnFullLife,
     OnLowLife,
     OnFullEnergyShield,
+    ArcaneSurge,
+    Elusive,
+    Tailwind,
+    UnholyMight,
+    Adrenaline,
 }
 
 pub fn int_data(p: Int) -> &'static IntData {
     match p {
-        Int::Level => &IntData {min: Val::Val(1), max: Val::Val(100)},
-        Int::FrenzyCharges => &IntData {min: Val::Stat(StatId::MinimumFrenzyCharges), max: Val::Stat(StatId::MaximumFrenzyCharges)},
-        Int::PowerCharges => &IntData {min: Val::Stat(StatId::MinimumPowerCharges), max: Val::Stat(StatId::MaximumPowerCharges)},
-        Int::EnduranceCharges => &IntData {min: Val::Stat(StatId::MinimumEnduranceCharges), max: Val::Stat(StatId::MaximumEnduranceCharges)},
-        Int::Rage => &IntData {min: Val::Stat(StatId::MinimumRage), max: Val::Stat(StatId::MaximumRage)},
-        Int::Fortification => &IntData {min: Val::Val(0), max: Val::Stat(StatId::MaximumFortification)},
+        Int::Level => &IntData {min: Val::Val(1), max: Val::Val(100), default: 1},
+        Int::FrenzyCharges => &IntData {min: Val::Stat(StatId::MinimumFrenzyCharges), max: Val::Stat(StatId::MaximumFrenzyCharges), default: 0},
+        Int::PowerCharges => &IntData {min: Val::Stat(StatId::MinimumPowerCharges), max: Val::Stat(StatId::MaximumPowerCharges), default: 0},
+        Int::EnduranceCharges => &IntData {min: Val::Stat(StatId::MinimumEnduranceCharges), max: Val::Stat(StatId::MaximumEnduranceCharges), default: 0},
+        Int::Rage => &IntData {min: Val::Stat(StatId::MinimumRage), max: Val::Stat(StatId::MaximumRage), default: 0},
+        Int::Fortification => &IntData {min: Val::Val(0), max: Val::Stat(StatId::MaximumFortification), default: 0},
+        Int::ImpaleStacks => &IntData {min: Val::Val(0), max: Val::Stat(StatId::MaximumImpales), default: 5},
+        Int::ActiveTotems => &IntData {min: Val::Val(1), max: Val::Stat(StatId::MaximumTotems), default: 1},
+        Int::ActiveTraps => &IntData {min: Val::Val(1), max: Val::Stat(StatId::MaximumTraps), default: 1},
+        Int::ActiveMines => &IntData {min: Val::Val(1), max: Val::Stat(StatId::MaximumMines), default: 1},
     }
 }
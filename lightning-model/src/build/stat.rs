@@ -1,5 +1,5 @@
 use rustc_hash::{FxHashMap, FxHashSet};
-use crate::{data::{base_item::ItemClass, gem::GemTag}, modifier::{Mod, Type}};
+use crate::{data::{base_item::ItemClass, gem::GemTag}, diagnostics::Diagnostics, modifier::{Mod, Type}};
 use lazy_static::lazy_static;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, strum_macros::Display, strum_macros::EnumCount)]
@@ -16,9 +16,20 @@ pub enum StatId {
     CooldownRecoverySpeed,
     ProjectileSpeed,
     TrapThrowingSpeed,
+    MineThrowingSpeed,
+    TotemPlacementSpeed,
+    MaximumTotems,
+    MaximumTraps,
+    MaximumMines,
+    AdditionalTraps,
+    AdditionalMines,
     ChanceToBlockAttackDamage,
     ChanceToBlockSpellDamage,
+    MaximumChanceToBlockAttackDamage,
+    MaximumChanceToBlockSpellDamage,
     ChanceToSuppressSpellDamage,
+    SuppressedSpellDamagePrevented,
+    DamageTakenFromManaBeforeLife,
     FireDotMultiplier,
     ColdDotMultiplier,
     ChaosDotMultiplier,
@@ -29,6 +40,11 @@ pub enum StatId {
     ChaosDamageOverTime,
     PhysicalDamageOverTime,
     DamageOverTime,
+    // Base damage over time of skills, per minute
+    BaseFireDamageOverTime,
+    BaseColdDamageOverTime,
+    BaseChaosDamageOverTime,
+    BasePhysicalDamageOverTime,
     BaseMinFireDamage,
     BaseMaxFireDamage,
     AddedMinFireDamage,
@@ -71,6 +87,8 @@ pub enum StatId {
     SkillEffectDuration,
     Duration,
     ImpaleEffect,
+    ChanceToImpale,
+    MaximumImpales,
     MinimumFrenzyCharges,
     MinimumPowerCharges,
     MinimumEnduranceCharges,
@@ -89,7 +107,37 @@ pub enum StatId {
     ManaRegeneration,
     ManaRegenerationPct,
     ManaRegenerationRate,
+    EnergyShieldRegeneration,
+    EnergyShieldRegenerationPct,
+    LifeRecoveryRate,
+    ManaRecoveryRate,
+    EnergyShieldRecoveryRate,
+    EnergyShieldRechargeStart,
+    Ward,
+    WardRestoration,
     ManaReservationEfficiency,
+    LifeReservationEfficiency,
+    LifeLeech,
+    PhysicalLifeLeech,
+    FireLifeLeech,
+    ColdLifeLeech,
+    LightningLifeLeech,
+    ChaosLifeLeech,
+    ManaLeech,
+    PhysicalManaLeech,
+    FireManaLeech,
+    ColdManaLeech,
+    LightningManaLeech,
+    ChaosManaLeech,
+    EnergyShieldLeech,
+    PhysicalEnergyShieldLeech,
+    FireEnergyShieldLeech,
+    ColdEnergyShieldLeech,
+    LightningEnergyShieldLeech,
+    ChaosEnergyShieldLeech,
+    MaximumLifeLeechRate,
+    MaximumManaLeechRate,
+    MaximumEnergyShieldLeechRate,
     CriticalStrikeChance,
     CriticalStrikeMultiplier,
     Armour,
@@ -108,6 +156,9 @@ pub enum StatId {
     FlaskEffectDuration,
     FlaskRecoveryRate,
     FlaskChargesUsed,
+    FlaskMaximumCharges,
+    FlaskEffect,
+    FlaskRecoveryAmount,
     ManaCost,
     LifeCost,
     Cost,
@@ -116,8 +167,12 @@ pub enum StatId {
     LightningDamagePen,
     ChaosDamagePen,
     ColdDamagePen,
+    FireExposure,
+    ColdExposure,
+    LightningExposure,
     ChanceToHit,
     ChanceToEvade,
+    ChanceToAvoidDamageFromHits,
     ChanceToDealDoubleDamage,
     PhysicalDamageReduction,
     ShockAsThoughDamage,
@@ -133,7 +188,29 @@ pub enum StatId {
     ChanceToIgnite,
     ChanceToFreeze,
     PoisonDuration,
+    IgniteDuration,
+    ShockDuration,
+    ChillDuration,
+    FreezeDuration,
+    ShockEffect,
+    ChillEffect,
+    DamageTaken,
+    PhysicalDamageTaken,
+    FireDamageTaken,
+    ColdDamageTaken,
+    LightningDamageTaken,
+    ChaosDamageTaken,
+    DamageOverTimeTaken,
+    PhysicalDamageOverTimeTaken,
+    ReflectedDamageTaken,
+    AilmentThreshold,
+    CurseEffectOnSelf,
+    IgniteDurationOnSelf,
+    PoisonDurationOnSelf,
     AuraEffect,
+    MaximumMinions,
+    CurseEffect,
+    CurseLimit,
     SmallPassiveIncreasedEffect,
     // Damage conversions (Physical → ...)
     PhysicalToLightningConversion,
@@ -149,8 +226,27 @@ pub enum StatId {
     ColdToChaosConversion,
     // Fire → ...
     FireToChaosConversion,
+    // Damage gained as extra (Physical → ...)
+    PhysicalGainedAsLightning,
+    PhysicalGainedAsCold,
+    PhysicalGainedAsFire,
+    PhysicalGainedAsChaos,
+    LightningGainedAsCold,
+    LightningGainedAsFire,
+    LightningGainedAsChaos,
+    ColdGainedAsFire,
+    ColdGainedAsChaos,
+    FireGainedAsChaos,
 }
 
+/// Leech of each recovered pool: (pool, leech from any damage, leech per damage type, maximum leech rate, recovery rate).
+/// Damage types are indexed like `DamageType::as_index`.
+pub const LEECH_STATS: [(StatId, StatId, [StatId; 5], StatId, StatId); 3] = [
+    (StatId::MaximumLife, StatId::LifeLeech, [StatId::PhysicalLifeLeech, StatId::FireLifeLeech, StatId::ColdLifeLeech, StatId::LightningLifeLeech, StatId::ChaosLifeLeech], StatId::MaximumLifeLeechRate, StatId::LifeRecoveryRate),
+    (StatId::MaximumMana, StatId::ManaLeech, [StatId::PhysicalManaLeech, StatId::FireManaLeech, StatId::ColdManaLeech, StatId::LightningManaLeech, StatId::ChaosManaLeech], StatId::MaximumManaLeechRate, StatId::ManaRecoveryRate),
+    (StatId::MaximumEnergyShield, StatId::EnergyShieldLeech, [StatId::PhysicalEnergyShieldLeech, StatId::FireEnergyShieldLeech, StatId::ColdEnergyShieldLeech, StatId::LightningEnergyShieldLeech, StatId::ChaosEnergyShieldLeech], StatId::MaximumEnergyShieldLeechRate, StatId::EnergyShieldRecoveryRate),
+];
+
 impl StatId {
     pub fn as_usize(self) -> usize {
         self as usize
@@ -160,6 +256,8 @@ impl StatId {
 #[derive(Debug, Clone, Default)]
 pub struct Stats {
     pub stats: FxHashMap<StatId, Stat>,
+    /// Issues met while evaluating, empty for the plain `calc_stats`
+    pub diagnostics: Diagnostics,
 }
 
 lazy_static! {
@@ -210,7 +308,7 @@ pub fn calc_stats(mods: &[Mod]) -> Stats {
         stats.entry(m.stat).or_default().adjust_mod(m);
     }
 
-    Stats { stats }
+    Stats { stats, diagnostics: Diagnostics::default() }
 }
 
 impl Default for Stat {
@@ -1,8 +1,9 @@
-use crate::build::stat::{Stat, StatId, Stats};
-use crate::build::{self, property, Build, Slot};
+use crate::build::stat::{Stat, StatId, Stats, LEECH_STATS};
+use crate::build::evaluator::{Evaluator, StatTrace};
+use crate::build::{self, property, Build, Slot, Warcry, WARCRY_STATS};
 use crate::data::base_item::ItemClass;
-use crate::data::gem::GemTag;
-use crate::data::{DamageGroup, DamageType, DAMAGE_GROUPS};
+use crate::data::gem::{ActiveSkillType, GemTag};
+use crate::data::{DamageGroup, DamageType, DAMAGE_GROUPS, MONSTER_STATS};
 use crate::gem::Gem;
 use crate::item::Item;
 use crate::modifier::{Mod, ModFlag, Source, Type};
@@ -57,6 +58,22 @@ fn conversion_stat_id(from_dt: DamageType, to_dt: DamageType) -> Option<StatId>
     }
 }
 
+fn gain_stat_id(from_dt: DamageType, to_dt: DamageType) -> Option<StatId> {
+    match (from_dt, to_dt) {
+        (DamageType::Physical, DamageType::Lightning) => Some(StatId::PhysicalGainedAsLightning),
+        (DamageType::Physical, DamageType::Cold)      => Some(StatId::PhysicalGainedAsCold),
+        (DamageType::Physical, DamageType::Fire)      => Some(StatId::PhysicalGainedAsFire),
+        (DamageType::Physical, DamageType::Chaos)     => Some(StatId::PhysicalGainedAsChaos),
+        (DamageType::Lightning, DamageType::Cold)     => Some(StatId::LightningGainedAsCold),
+        (DamageType::Lightning, DamageType::Fire)     => Some(StatId::LightningGainedAsFire),
+        (DamageType::Lightning, DamageType::Chaos)    => Some(StatId::LightningGainedAsChaos),
+        (DamageType::Cold, DamageType::Fire)          => Some(StatId::ColdGainedAsFire),
+        (DamageType::Cold, DamageType::Chaos)         => Some(StatId::ColdGainedAsChaos),
+        (DamageType::Fire, DamageType::Chaos)         => Some(StatId::FireGainedAsChaos),
+        _ => None,
+    }
+}
+
 fn conversion_targets(from_dt: DamageType) -> &'static [DamageType] {
     match from_dt {
         DamageType::Physical  => &[DamageType::Lightning, DamageType::Cold, DamageType::Fire, DamageType::Chaos],
@@ -68,7 +85,7 @@ fn conversion_targets(from_dt: DamageType) -> &'static [DamageType] {
 }
 
 /// Apply the damage conversion chain, tracking which source types contributed
-/// to each portion.
+/// to each portion. Damage gained as extra is taken from the damage before it gets converted.
 fn apply_conversion(stats: &Stats, base_damages: &[i64; 5]) -> [Vec<DamagePortion>; 5] {
     let mut portions: [Vec<DamagePortion>; 5] = Default::default();
 
@@ -85,7 +102,11 @@ fn apply_conversion(stats: &Stats, base_damages: &[i64; 5]) -> [Vec<DamagePortio
             conversion_stat_id(from_dt, to_dt).map(|sid| stats.val(sid))
         }).sum();
 
-        if total_conv == 0 { continue; }
+        let total_gain: i64 = targets.iter().filter_map(|&to_dt| {
+            gain_stat_id(from_dt, to_dt).map(|sid| stats.val(sid))
+        }).sum();
+
+        if total_conv == 0 && total_gain == 0 { continue; }
 
         let remaining_pct = (100 - total_conv.min(100)).max(0);
         let current_portions = std::mem::take(&mut portions[from_idx]);
@@ -111,6 +132,15 @@ fn apply_conversion(stats: &Stats, base_damages: &[i64; 5]) -> [Vec<DamagePortio
                         });
                     }
                 }
+                if let Some(stat_id) = gain_stat_id(from_dt, to_dt) {
+                    let gain_pct = stats.val(stat_id);
+                    if gain_pct > 0 {
+                        portions[to_idx].push(DamagePortion {
+                            amount: (portion.amount * gain_pct) / 100,
+                            source_types: portion.source_types | DAMAGE_GROUPS[to_idx].damage_type,
+                        });
+                    }
+                }
             }
         }
     }
@@ -144,6 +174,55 @@ fn apply_damage_mods_portions(portions: &[Vec<DamagePortion>; 5], stats: &Stats,
     result
 }
 
+/// Damage over time stat and damage over time multiplier stat for a damage type
+fn dot_stat_ids(dt: DamageType) -> Option<(StatId, StatId)> {
+    match dt {
+        DamageType::Physical => Some((StatId::PhysicalDamageOverTime, StatId::PhysicalDotMultiplier)),
+        DamageType::Fire     => Some((StatId::FireDamageOverTime, StatId::FireDotMultiplier)),
+        DamageType::Cold     => Some((StatId::ColdDamageOverTime, StatId::ColdDotMultiplier)),
+        DamageType::Chaos    => Some((StatId::ChaosDamageOverTime, StatId::ChaosDotMultiplier)),
+        DamageType::Lightning => None,
+    }
+}
+
+/// Apply inc/more modifiers to the portions of the `from` damage types that are
+/// dealt as damage over time of type `dt` by an ailment.
+/// Damage over time modifiers join the same inc pool as the regular damage modifiers.
+fn apply_dot_mods_portions(portions: &[Vec<DamagePortion>; 5], stats: &Stats, weapon: Option<ItemClass>, from: BitFlags<DamageType>, dt: DamageType) -> i64 {
+    let mut generic = stats.stat(StatId::Damage).with_weapon(weapon);
+    generic.assimilate(&stats.stat(StatId::DamageOverTime).with_weapon(weapon));
+    if let Some((dot_id, _)) = dot_stat_ids(dt) {
+        generic.assimilate(&stats.stat(dot_id).with_weapon(weapon));
+    }
+
+    let mut result = 0;
+    for portion in from.iter().flat_map(|from_dt| &portions[from_dt.as_index()]) {
+        let mut inc = generic.inc;
+        let mut more = generic.more;
+
+        for dg in &DAMAGE_GROUPS {
+            if (portion.source_types | dt).contains(dg.damage_type) {
+                let type_stat = stats.stat(dg.stat_id).with_weapon(weapon);
+                inc += type_stat.inc;
+                more = (more * type_stat.more) / 100;
+            }
+        }
+
+        result += (portion.amount * (100 + inc) * more) / 10000;
+    }
+
+    result
+}
+
+/// Total damage over time multiplier (generic + damage type specific)
+fn dot_multiplier(stats: &Stats, dt: DamageType) -> i64 {
+    let mut dot_multi = stats.stat(StatId::DotMultiplier).to_owned();
+    if let Some((_, multi_id)) = dot_stat_ids(dt) {
+        dot_multi.assimilate(stats.stat(multi_id));
+    }
+    dot_multi.val()
+}
+
 pub fn compare(a: &FxHashMap<&'static str, i64>, b: &FxHashMap<&'static str, i64>) -> FxHashMap<&'static str, i64> {
     let mut result = FxHashMap::default();
     for key in a.keys().chain(b.keys()) {
@@ -215,6 +294,94 @@ fn calc_weapon_bleed_dmg(stats: &Stats, weapon: &Item, active_gem: &Gem, dg: &Da
     0
 }
 
+const IGNITE_BASE_DURATION: i64 = 4000;
+
+/// Average ignite DPS from the fire portions of a hit, igniting with `chance` (chance to hit included).
+/// Only the strongest ignite applies, so crits are averaged in rather than stacked.
+fn calc_ignite_dps(stats_ignite: &Stats, portions: &[Vec<DamagePortion>; 5], weapon: Option<ItemClass>, crit_chance: i64, chance: i64) -> i64 {
+    let fire_damage = apply_dot_mods_portions(portions, stats_ignite, weapon, DamageType::Fire.into(), DamageType::Fire);
+    if fire_damage <= 0 {
+        return 0;
+    }
+    let crit_multi = stats_ignite.val(StatId::CriticalStrikeMultiplier);
+    let mut ignite = Stat::default();
+    ignite.adjust_mod(&Mod { typ: Type::Base, amount: calc_dmg_crit_accuracy(fire_damage, crit_chance, crit_multi, 100), ..Default::default() });
+    ignite.adjust_mod(&Mod { typ: Type::More, amount: -10, source: Source::Custom("Ignites deal 90%"), ..Default::default() });
+    ignite.adjust_mod(&Mod { typ: Type::More, amount: dot_multiplier(stats_ignite, DamageType::Fire), source: Source::Custom("Ignite Multi"), ..Default::default() });
+    (ignite.val() * chance.clamp(0, 100)) / 100
+}
+
+const POISON_BASE_DURATION: i64 = 2000;
+
+/// Damage per second of a single poison from the physical and chaos portions of a hit.
+/// Poisons stack, so the total depends on how many are applied during their duration.
+fn calc_single_poison_dps(stats_poison: &Stats, portions: &[Vec<DamagePortion>; 5], weapon: Option<ItemClass>, crit_chance: i64) -> i64 {
+    let poison_damage = apply_dot_mods_portions(portions, stats_poison, weapon, make_bitflags!(DamageType::{Physical | Chaos}), DamageType::Chaos);
+    if poison_damage <= 0 {
+        return 0;
+    }
+    let crit_multi = stats_poison.val(StatId::CriticalStrikeMultiplier);
+    let mut poison = Stat::default();
+    poison.adjust_mod(&Mod { typ: Type::Base, amount: calc_dmg_crit_accuracy(poison_damage, crit_chance, crit_multi, 100), ..Default::default() });
+    poison.adjust_mod(&Mod { typ: Type::More, amount: -70, source: Source::Custom("Poisons deal 30%"), ..Default::default() });
+    poison.adjust_mod(&Mod { typ: Type::More, amount: dot_multiplier(stats_poison, DamageType::Chaos), source: Source::Custom("Poison Multi"), ..Default::default() });
+    poison.val()
+}
+
+/// Average damage dealt by the impales on the enemy each time it is hit.
+/// An impale stores 10% of the physical damage of the hit that inflicted it.
+fn calc_impale_damage(stats: &Stats, physical_damage: i64, crit_chance: i64, crit_multi: i64, impale_chance: i64, impales: i64) -> i64 {
+    let stored = calc_dmg_crit_accuracy(physical_damage, crit_chance, crit_multi, 100) / 10;
+    (stats.stat(StatId::ImpaleEffect).val_custom(stored) * impales * impale_chance) / 100
+}
+
+const SHOCK_BASE_DURATION: i64 = 2000;
+const CHILL_BASE_DURATION: i64 = 2000;
+const FREEZE_MIN_DURATION: i64 = 300;
+const FREEZE_MAX_DURATION: i64 = 3000;
+
+/// Effect of a shock or chill before modifiers, from the hit damage relative to the enemy's ailment threshold
+fn calc_ailment_base_effect(damage: i64, ailment_threshold: i64) -> i64 {
+    if damage <= 0 || ailment_threshold <= 0 {
+        return 0;
+    }
+    (50.0 * (damage as f32 / ailment_threshold as f32).powf(0.4)) as i64
+}
+
+/// Increased damage taken by the enemy from a shock.
+/// Shocks below 5% are not applied.
+fn calc_shock_effect(stats: &Stats, lightning_damage: i64, ailment_threshold: i64) -> i64 {
+    let shock_damage = (lightning_damage * (100 + stats.val(StatId::ShockAsThoughDamage))) / 100;
+    let effect = stats.stat(StatId::ShockEffect).val_custom(calc_ailment_base_effect(shock_damage, ailment_threshold)).min(50);
+    if effect < 5 { 0 } else { effect }
+}
+
+/// Average increased damage taken by the enemy from shocks of `effect` applied with `chance`,
+/// the enemy staying unshocked the rest of the time
+fn calc_average_shock(effect: i64, chance: i64) -> i64 {
+    (effect * chance.clamp(0, 100)) / 100
+}
+
+/// Damage of a type dealt by a hit once the enemy's resistance applies, which sizes the ailments it inflicts
+fn calc_ailment_hit(stats: &Stats, monster_stats: &Stats, final_damages: &[i64; 5], dt: DamageType) -> i64 {
+    apply_enemy_resistance(final_damages[dt.as_index()], calc_enemy_resistance(stats, monster_stats, dt, true))
+}
+
+/// Reduced action speed of the enemy from a chill.
+/// Chills below 5% are not applied.
+fn calc_chill_effect(stats: &Stats, cold_damage: i64, ailment_threshold: i64) -> i64 {
+    let effect = stats.stat(StatId::ChillEffect).val_custom(calc_ailment_base_effect(cold_damage, ailment_threshold)).min(30);
+    if effect < 5 { 0 } else { effect }
+}
+
+/// Freeze duration in ms, 0 if the hit is too weak to freeze.
+fn calc_freeze_duration(stats: &Stats, cold_damage: i64, ailment_threshold: i64) -> i64 {
+    if cold_damage <= 0 || ailment_threshold <= 0 {
+        return 0;
+    }
+    let duration = stats.stat(StatId::FreezeDuration).val_custom((60 * cold_damage) / ailment_threshold).min(FREEZE_MAX_DURATION);
+    if duration < FREEZE_MIN_DURATION { 0 } else { duration }
+}
 
 fn calc_crit_chance(stats: &Stats, crit_chance: Option<i64>) -> i64 {
     let mut crit_chance_stat = stats.stat(StatId::CriticalStrikeChance).to_owned();
@@ -225,9 +392,13 @@ fn calc_crit_chance(stats: &Stats, crit_chance: Option<i64>) -> i64 {
 }
 
 fn calc_chance_hit_weapon(stats: &Stats, monster_stats: &Stats, weapon: &Item) -> i64 {
-    let mut chance_to_hit_stat = stats.stat(StatId::ChanceToHit).to_owned();
     let mut accuracy_stat = stats.stat(StatId::AccuracyRating).to_owned();
     accuracy_stat.assimilate(&weapon.accuracy());
+    calc_chance_hit(stats, monster_stats, &accuracy_stat)
+}
+
+fn calc_chance_hit(stats: &Stats, monster_stats: &Stats, accuracy_stat: &Stat) -> i64 {
+    let mut chance_to_hit_stat = stats.stat(StatId::ChanceToHit).to_owned();
     let accuracy = accuracy_stat.val() as f32;
     let monster_evasion = monster_stats.val(StatId::EvasionRating) as f32;
     let chance_to_hit_from_accuracy = ((((1.25 * accuracy) / (accuracy + (monster_evasion * 0.2).powf(0.9))) * 100.0) as i64).clamp(0, 100);
@@ -240,16 +411,369 @@ fn physical_damage_reduction_armour(amount: i64, armour: i64, pdr: i64) -> i64 {
     pdr + pdr_from_armour
 }
 
-pub fn calc_gem<'a>(build: &Build, support_gems: &[&Gem], active_gem: &Gem) -> FxHashMap<&'static str, i64> {
+/// Damage per second of a skill dealing base damage over time of a single type
+fn calc_skill_dot_dps(stats_dot: &Stats, dt: DamageType) -> i64 {
+    let base_id = match dt {
+        DamageType::Physical => StatId::BasePhysicalDamageOverTime,
+        DamageType::Fire => StatId::BaseFireDamageOverTime,
+        DamageType::Cold => StatId::BaseColdDamageOverTime,
+        DamageType::Chaos => StatId::BaseChaosDamageOverTime,
+        DamageType::Lightning => return 0,
+    };
+    let base = stats_dot.val(base_id) / 60;
+    if base <= 0 {
+        return 0;
+    }
+    let mut portions: [Vec<DamagePortion>; 5] = Default::default();
+    portions[dt.as_index()].push(DamagePortion { amount: base, source_types: dt.into() });
+
+    let mut dot = Stat::default();
+    dot.adjust_mod(&Mod { typ: Type::Base, amount: apply_dot_mods_portions(&portions, stats_dot, None, dt.into(), dt), ..Default::default() });
+    dot.adjust_mod(&Mod { typ: Type::More, amount: dot_multiplier(stats_dot, dt), source: Source::Custom("DoT Multi"), ..Default::default() });
+    dot.val()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Trigger {
+    CastOnCrit,
+    CastWhileChannelling,
+    ArcanistBrand,
+}
+
+const TRIGGERS: [(&str, Trigger); 3] = [
+    ("Cast On Critical Strike Support", Trigger::CastOnCrit),
+    ("Cast while Channelling Support", Trigger::CastWhileChannelling),
+    ("Arcanist Brand", Trigger::ArcanistBrand),
+];
+
+const CAST_WHILE_CHANNELLING_INTERVAL: i64 = 350;
+const ARCANIST_BRAND_INTERVAL: i64 = 400;
+/// Cooldown of Cast On Critical Strike when it comes from an item rather than a gem
+const CAST_ON_CRIT_COOLDOWN: i64 = 150;
+
+fn trigger_kind(gem: &Gem) -> Option<Trigger> {
+    let name = gem.data().base_item.display_name.trim_start_matches("Awakened ");
+    TRIGGERS.iter().find(|(trigger_name, _)| *trigger_name == name).map(|(_, trigger)| *trigger)
+}
+
+/// Trigger granted by an item mod, like "Socketed Gems are Supported by Level 20 Cast On Critical Strike".
+/// None if the mod isn't a trigger, Some(None) if it's one the calculations don't support,
+/// e.g. "Trigger Level 20 Bone Nova when you Kill" or supports like Cast when Damage Taken.
+pub(crate) fn item_mod_trigger(text: &str) -> Option<Option<Trigger>> {
+    let text = text.to_lowercase();
+    if let Some(support) = text.strip_prefix("socketed gems are supported by level ").and_then(|rest| rest.split_once(' ')).map(|(_, support)| support) {
+        let trigger = TRIGGERS.iter().find(|(name, _)| name.trim_end_matches(" Support").eq_ignore_ascii_case(support)).map(|(_, trigger)| *trigger);
+        if trigger.is_some() || support.starts_with("cast ") || support.contains("trigger") {
+            return Some(trigger);
+        }
+        return None;
+    }
+    (text.starts_with("trigger ") || text.contains(" trigger ")).then_some(None)
+}
+
+/// Trigger of an equipped item granting it to the gems socketed in `slot`
+fn item_trigger_kind(build: &Build, slot: Slot) -> Option<Trigger> {
+    let item = build.get_equipped(slot)?;
+    item.mods_impl.iter().chain(&item.mods_expl).chain(&item.mods_enchant).find_map(|text| item_mod_trigger(text).flatten())
+}
+
+/// Rounds a duration up to the next server tick (30 per second)
+fn round_server_tick(ms: i64) -> i64 {
+    let ticks = (ms * 30 + 999) / 1000;
+    (ticks * 1000) / 30
+}
+
+/// Time in ms between two crits of an attack, crit chance in hundredths of a percent.
+/// 0 when the attack never crits.
+fn calc_crit_trigger_time(attack_time: i64, crit_chance: i64, chance_to_hit: i64) -> i64 {
+    let trigger_chance = (crit_chance * chance_to_hit) / 100;
+    if attack_time <= 0 || trigger_chance <= 0 {
+        return 0;
+    }
+    (attack_time * 10000) / trigger_chance
+}
+
+/// Time in ms between two casts of `active_gem` if it is triggered by a support, by another
+/// gem of its link or by the item it is socketed in rather than self-cast.
+/// Returns (time between casts, cooldown of the trigger after recovery and tick rounding).
+fn calc_trigger_time(build: &Build, warcries: &[WarcryUse], stats: &Stats, support_gems: &[&Gem], active_gem: &Gem) -> Option<(i64, i64)> {
+    let gem_link = build.gem_links.iter().find(|l| l.gems.iter().any(|g| std::ptr::eq(g.as_ref(), active_gem)))?;
+    let (trigger, trigger_gem) = support_gems.iter().copied()
+        .chain(gem_link.active_gems().filter(|g| g.enabled).map(|g| g.as_ref()))
+        .filter(|g| !std::ptr::eq(*g, active_gem))
+        .find_map(|g| trigger_kind(g).map(|t| (t, Some(g))))
+        .or_else(|| item_trigger_kind(build, gem_link.slot).map(|t| (t, None)))?;
+
+    let trigger_cooldown = match trigger_gem {
+        Some(gem) => gem.cooldown().unwrap_or(0),
+        None if trigger == Trigger::CastOnCrit => CAST_ON_CRIT_COOLDOWN,
+        None => 0,
+    };
+    let cooldown = trigger_cooldown.max(active_gem.cooldown().unwrap_or(0));
+    let cooldown = round_server_tick(stats.stat(StatId::CooldownRecoverySpeed).val_custom_inv(cooldown));
+
+    let source_time = match trigger {
+        Trigger::CastOnCrit => {
+            // Attack of the link doing the triggering, each of its crits is a trigger attempt.
+            // Without an attack or crits, the spell is never cast rather than self-cast.
+            let Some(source_gem) = gem_link.active_gems().find(|g| g.enabled && g.data().tags.contains(&GemTag::Attack)) else {
+                return Some((0, cooldown));
+            };
+            let source_calc = calc_gem_with_warcries(build, warcries, support_gems, source_gem);
+            let attack_time = source_calc.get("Speed").copied().unwrap_or(0);
+            let crit_chance = source_calc.get("Crit Chance (MH)").or(source_calc.get("Crit Chance (OH)")).copied().unwrap_or(0);
+            let chance_to_hit = source_calc.get("Chance to Hit (MH)").or(source_calc.get("Chance to Hit (OH)")).copied().unwrap_or(100);
+            let trigger_time = calc_crit_trigger_time(attack_time, crit_chance, chance_to_hit);
+            if trigger_time == 0 {
+                return Some((0, cooldown));
+            }
+            trigger_time
+        },
+        Trigger::CastWhileChannelling => round_server_tick(trigger_gem.and_then(|g| g.stat_value("cast_while_channelling_time_ms")).unwrap_or(CAST_WHILE_CHANNELLING_INTERVAL)),
+        Trigger::ArcanistBrand => round_server_tick(ARCANIST_BRAND_INTERVAL),
+    };
+    Some((source_time.max(cooldown), cooldown))
+}
+
+/// Maximum recovered by a single leech instance, percentage of the pool
+const LEECH_INSTANCE_MAX: i64 = 10;
+
+/// Recovery per second of life, mana and energy shield from leeching the hits of the skill.
+/// Each hit is capped per instance, the sum by the maximum leech rate of the pool,
+/// before recovery rate modifiers.
+fn calc_leech(stats: &Stats, damage_instances: &[DamageInstance], crit_multi: i64, damage_taken: &Stat, time: i64) -> [i64; 3] {
+    LEECH_STATS.map(|(pool, any, per_type, max_rate, recovery_rate)| {
+        let pool = stats.val(pool);
+        let leech_per_hit: i64 = damage_instances.iter().map(|inst| {
+            let leech: i64 = inst.instance_type.iter().map(|t| {
+                let damage = damage_taken.val_custom(calc_dmg_crit_accuracy(t.amount, t.crit_chance, crit_multi, t.chance_to_hit));
+                (damage * (stats.val(any) + stats.val(per_type[t.typ.as_index()]))) / 10000
+            }).sum();
+            leech.min((pool * LEECH_INSTANCE_MAX) / 100)
+        }).sum();
+        let leech = ((leech_per_hit * 1000) / time).min((pool * stats.val(max_rate)) / 100);
+        stats.stat(recovery_rate).val_custom(leech)
+    })
+}
+
+/// A warcry used whenever its cooldown is over
+struct WarcryUse {
+    warcry: &'static Warcry,
+    /// Time in ms between two uses
+    cycle: i64,
+    /// Percentage of the time its buffs are active
+    uptime: i64,
+}
+
+/// Time taken by an action, sped up by its own speed stat and by action speed
+fn calc_action_time(stats: &Stats, speed: StatId, time: i64) -> i64 {
+    stats.stat(StatId::ActionSpeed).val_custom_inv(stats.stat(speed).val_custom_inv(time))
+}
+
+/// How often each warcry of the build is used, evaluated once per build evaluation and shared by its skills
+fn calc_warcry_uses(build: &Build) -> Vec<WarcryUse> {
+    let player_mods = build.calc_mods(true);
+    build.calc_warcries().into_iter().map(|gem| calc_warcry_use(build, &player_mods, gem)).collect()
+}
+
+fn calc_warcry_use(build: &Build, player_mods: &[Mod], gem: &Gem) -> WarcryUse {
+    let warcry = &WARCRY_STATS[gem.data().base_item.display_name.as_str()];
+    let mut mods = player_mods.to_vec();
+    mods.extend_from_slice(&gem.calc_mods(false));
+    let tags = gem.data().tags.iter().copied().map(BitFlags::from).fold(BitFlags::empty(), |acc, flag| acc | flag);
+    let stats = build.calc_stats(&mods, tags, make_bitflags!(ModFlag::{Aura | Buff}));
+
+    let cooldown = stats.stat(StatId::CooldownRecoverySpeed).val_custom_inv(gem.cooldown().unwrap_or(0));
+    let cast_time = calc_action_time(&stats, StatId::WarcrySpeed, gem.data().cast_time.unwrap_or(0));
+    let cycle = cooldown.max(cast_time);
+    let duration = stats.stat(StatId::SkillEffectDuration).val_custom(gem.stat_value("base_skill_effect_duration").unwrap_or(warcry.duration));
+    let uptime = if cycle > 0 { ((duration * 100) / cycle).min(100) } else { 100 };
+    WarcryUse { warcry, cycle, uptime }
+}
+
+/// Scales the buffs or debuffs of a warcry by its uptime
+fn warcry_mods(mods: &[Mod], uptime: i64) -> impl Iterator<Item = Mod> + '_ {
+    mods.iter().map(move |m| Mod {
+        revised_amount: Some((m.final_amount() * uptime) / 100),
+        flags: m.flags | ModFlag::Buff,
+        ..*m
+    })
+}
+
+/// Average more damage of attacks, with the exerted attacks of each warcry
+/// spread over all the attacks made between two uses of that warcry
+fn calc_exert_more_damage(warcries: &[WarcryUse], attack_time: i64) -> i64 {
+    warcries.iter().fold(100, |acc, w| {
+        let exerted = if w.cycle > 0 { ((w.warcry.exerted_attacks * attack_time * 100) / w.cycle).min(100) } else { 100 };
+        (acc * (100 + (w.warcry.exert_more_damage * exerted) / 100)) / 100
+    }) - 100
+}
+
+/// Skills used by a totem, trap or mine instead of by the player
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Deployer {
+    Totem,
+    Trap,
+    Mine,
+}
+
+const TOTEM_PLACEMENT_TIME: i64 = 600;
+const TRAP_THROWING_TIME: i64 = 600;
+const MINE_THROWING_TIME: i64 = 300;
+
+/// Traps or mines thrown at once, limited by how many can be active
+fn calc_thrown_at_once(additional: i64, max_active: i64) -> i64 {
+    (1 + additional.max(0)).min(max_active)
+}
+
+/// Deployer of the skill, from the tags of the skill or the supports able to support it (e.g. Spell Totem)
+fn find_deployer(support_gems: &[&Gem], active_gem: &Gem) -> Option<Deployer> {
+    let gems = || std::iter::once(active_gem).chain(support_gems.iter().copied().filter(|g| g.can_support(active_gem)));
+    if gems().any(|g| g.data().tags.contains(&GemTag::Totem)) {
+        Some(Deployer::Totem)
+    } else if gems().any(|g| g.data().tags.contains(&GemTag::Trap)) {
+        Some(Deployer::Trap)
+    } else if gems().any(|g| g.data().tags.contains(&GemTag::Mine)) {
+        Some(Deployer::Mine)
+    } else {
+        None
+    }
+}
+
+/// Offence and defence of the minions created by `active_gem`, from the monster stats of the gem.
+/// `mods` are the mods of the skill, from which the minion ones are picked.
+/// Empty without minion data, see `Build::diagnostics`.
+fn calc_minion(build: &Build, mods: &[Mod], stats: &Stats, active_gem: &Gem, monster_stats: &Stats) -> FxHashMap<&'static str, i64> {
+    let mut ret = FxHashMap::default();
+    let Some(minion) = active_gem.data().minion.as_ref() else {
+        return ret;
+    };
+    let level = active_gem.stat_value("display_minion_monster_level").unwrap_or(build.property_int(property::Int::Level)).clamp(1, 100);
+    let minion_mods = Build::calc_mods_minion(level, minion, mods);
+    // Minion skills are not in the gem data, the minion attacks with the stats of its monster
+    let minion_stats = build.calc_stats(&minion_mods, GemTag::Attack.into(), make_bitflags!(ModFlag::{Hit | Aura | Buff}));
+
+    ret.insert("Minion Life", minion_stats.stat(StatId::MaximumLife).val_ceil());
+    for (label, res_id, max_res_id) in [
+        ("Minion Fire Resistance", StatId::FireResistance, StatId::MaximumFireResistance),
+        ("Minion Cold Resistance", StatId::ColdResistance, StatId::MaximumColdResistance),
+        ("Minion Lightning Resistance", StatId::LightningResistance, StatId::MaximumLightningResistance),
+        ("Minion Chaos Resistance", StatId::ChaosResistance, StatId::MaximumChaosResistance),
+    ] {
+        ret.insert(label, minion_stats.val(res_id).min(minion_stats.val(max_res_id)));
+    }
+
+    let physical_damage = MONSTER_STATS.get(&level).map_or(0, |m| (m.physical_damage as i64 * minion.damage_multiplier) / 100);
+    let portions = apply_conversion(&minion_stats, &[physical_damage, 0, 0, 0, 0]);
+    let final_damages = apply_damage_mods_portions(&portions, &minion_stats, None);
+    let chance_to_hit = calc_chance_hit(&minion_stats, monster_stats, minion_stats.stat(StatId::AccuracyRating));
+    let crit_chance = calc_crit_chance(&minion_stats, Some(minion.crit_chance));
+    let crit_multi = minion_stats.val(StatId::CriticalStrikeMultiplier);
+
+    let mut average_damage = 0;
+    for (i, dg) in DAMAGE_GROUPS.iter().enumerate() {
+        let mut avg_damage = final_damages[i];
+        if avg_damage <= 0 { continue; }
+        if dg.damage_type == DamageType::Physical {
+            let pdr = physical_damage_reduction_armour(avg_damage, monster_stats.val(StatId::Armour), 0);
+            avg_damage = (avg_damage * (100 - pdr)) / 100;
+        } else {
+            avg_damage = apply_enemy_resistance(avg_damage, calc_enemy_resistance(&minion_stats, monster_stats, dg.damage_type, true));
+        }
+        average_damage += calc_dmg_crit_accuracy(avg_damage, crit_chance, crit_multi, chance_to_hit);
+    }
+    let average_damage = monster_stats.stat(StatId::DamageTaken).val_custom(average_damage);
+
+    let time = calc_action_time(&minion_stats, StatId::AttackSpeed, minion.attack_time);
+    if time > 0 {
+        let minion_dps = (average_damage * 1000) / time;
+        let count = stats.val(StatId::MaximumMinions).max(1);
+        ret.insert("Minion DPS", minion_dps);
+        ret.insert("Minion Count", count);
+        ret.insert("Total Minion DPS", minion_dps * count);
+    }
+    ret
+}
+
+const MONSTER_MAX_RESISTANCE: i64 = 75;
+
+/// Exposure doesn't stack, only the strongest applies, scaled by the modifiers to exposure
+fn calc_exposure(exposure: &Stat) -> i64 {
+    let strongest = exposure.mods.iter().filter(|m| matches!(m.typ, Type::Base)).map(|m| m.final_amount()).max().unwrap_or(0);
+    (strongest * exposure.mult()) / 10000
+}
+
+/// Enemy resistance against a damage type once exposure, and for hits penetration, are applied.
+/// Physical damage is mitigated by armour instead.
+fn calc_enemy_resistance(stats: &Stats, monster_stats: &Stats, dt: DamageType, hit: bool) -> i64 {
+    let (res_id, pen_id, exposure_id) = match dt {
+        DamageType::Physical => return 0,
+        DamageType::Fire => (StatId::FireResistance, StatId::FireDamagePen, Some(StatId::FireExposure)),
+        DamageType::Cold => (StatId::ColdResistance, StatId::ColdDamagePen, Some(StatId::ColdExposure)),
+        DamageType::Lightning => (StatId::LightningResistance, StatId::LightningDamagePen, Some(StatId::LightningExposure)),
+        DamageType::Chaos => (StatId::ChaosResistance, StatId::ChaosDamagePen, None),
+    };
+    let mut resistance = monster_stats.val(res_id);
+    if let Some(exposure_id) = exposure_id {
+        resistance -= calc_exposure(stats.stat(exposure_id));
+    }
+    resistance = resistance.min(MONSTER_MAX_RESISTANCE);
+    if hit {
+        // Penetration is stored x100
+        resistance -= stats.val(pen_id) / 100;
+    }
+    resistance
+}
+
+fn apply_enemy_resistance(amount: i64, resistance: i64) -> i64 {
+    (amount * (100 - resistance)) / 100
+}
+
+const LEECH_LABELS: [&str; 3] = ["Life Leech/s", "Mana Leech/s", "ES Leech/s"];
+
+/// Whether the skill calculated by `calc_gem` recovers anything from leech
+pub fn is_leeching(skill: &FxHashMap<&'static str, i64>) -> bool {
+    LEECH_LABELS.iter().any(|label| skill.get(label).is_some_and(|leech| *leech > 0))
+}
+
+/// Whether hits with `mods` leech, only evaluating the leech stats.
+/// Mods applying while leeching can't make the skill leech themselves.
+fn skill_leeches(build: &Build, mods: &[Mod], tags: BitFlags<GemTag>) -> bool {
+    let mut evaluator = Evaluator::new(build, mods, tags, make_bitflags!(ModFlag::{Hit | Aura | Buff}));
+    LEECH_STATS.iter().any(|(_, any, per_type, _, _)| {
+        std::iter::once(any).chain(per_type).any(|stat_id| evaluator.get_stat_val(*stat_id) > 0)
+    })
+}
+
+pub fn calc_gem(build: &Build, support_gems: &[&Gem], active_gem: &Gem) -> FxHashMap<&'static str, i64> {
+    calc_gem_with_warcries(build, &calc_warcry_uses(build), support_gems, active_gem)
+}
+
+/// `calc_gem` with the warcry uses of the build already evaluated
+fn calc_gem_with_warcries(build: &Build, warcries: &[WarcryUse], support_gems: &[&Gem], active_gem: &Gem) -> FxHashMap<&'static str, i64> {
     assert!(!active_gem.data().is_support);
     let mut ret = FxHashMap::default();
 
     // convert HashSet<GemTag> into BitFlags
-    let tags = active_gem.data().tags.iter().copied().map(BitFlags::from).fold(BitFlags::empty(), |acc, flag| acc | flag);
+    let mut tags = active_gem.data().tags.iter().copied().map(BitFlags::from).fold(BitFlags::empty(), |acc, flag| acc | flag);
     let mut damage = vec![];
 
-    let mut mods = build.calc_mods(true);
+    // Skills used by a totem/trap/mine benefit from the modifiers to totem/trap/mine skills
+    let deployer = find_deployer(support_gems, active_gem);
+    match deployer {
+        Some(Deployer::Totem) => tags.insert(GemTag::Totem),
+        Some(Deployer::Trap) => tags.insert(GemTag::Trap),
+        Some(Deployer::Mine) => tags.insert(GemTag::Mine),
+        None => (),
+    }
+
+    let player_mods = build.calc_mods(true);
+
+    let mut mods = player_mods.clone();
     mods.extend_from_slice(&active_gem.calc_mods(false));
+    for warcry in warcries {
+        mods.extend(warcry_mods(&warcry.warcry.buffs, warcry.uptime));
+    }
 
     let mut best_supports: FxHashMap<&str, &Gem> = FxHashMap::default();
     for support_gem in support_gems {
@@ -267,19 +791,51 @@ pub fn calc_gem<'a>(build: &Build, support_gems: &[&Gem], active_gem: &Gem) -> F
         mods.extend_from_slice(&support_gem.calc_mods(false));
     }
 
-    let stats = build.calc_stats(&mods, tags, make_bitflags!(ModFlag::{Hit | Aura | Buff}));
-    let stats_bleed = build.calc_stats(&mods, tags, make_bitflags!(ModFlag::{Ailment | Bleed | Aura | Buff}));
-
-    let monster_mods = Build::calc_mods_monster(build.property_int(property::Int::Level).min(83));
+    // Skills used by a deployer don't leech for the player
+    let leeching = deployer.is_none() && (tags.contains(GemTag::Attack) || tags.contains(GemTag::Spell)) && skill_leeches(build, &mods, tags);
+    let stats = build.calc_stats_leeching(&mods, tags, make_bitflags!(ModFlag::{Hit | Aura | Buff}), leeching);
+    let stats_bleed = build.calc_stats_leeching(&mods, tags, make_bitflags!(ModFlag::{Ailment | Bleed | Aura | Buff}), leeching);
+    let stats_ignite = build.calc_stats_leeching(&mods, tags, make_bitflags!(ModFlag::{Ailment | Ignite | Aura | Buff}), leeching);
+    let stats_poison = build.calc_stats_leeching(&mods, tags, make_bitflags!(ModFlag::{Ailment | Poison | Aura | Buff}), leeching);
+    let stats_dot = build.calc_stats_leeching(&mods, tags, make_bitflags!(ModFlag::{Aura | Buff}), leeching);
+
+    let mut monster_mods = Build::calc_mods_monster(build.property_int(property::Int::Level).min(83), &build.enemy);
+    monster_mods.extend(build.calc_map_mods(true));
+    // Curses come scaled by the curse effect of their own gem, the enemy's curse effect reduction
+    // scales them all. They only apply to skills with their tags.
+    let curse_mult = build::stat::calc_stats(&monster_mods).stat(StatId::CurseEffectOnSelf).mult();
+    monster_mods.extend(build.calc_curses(&player_mods).into_iter().filter(|m| tags.contains(m.tags)).map(|mut m| {
+        m.revised_amount = Some((m.final_amount() * curse_mult) / 10000);
+        m
+    }));
+    // Warcry debuffs like Intimidate also only apply to the skills with their tags
+    for warcry in warcries {
+        monster_mods.extend(warcry_mods(&warcry.warcry.debuffs, warcry.uptime).filter(|m| tags.contains(m.tags)));
+    }
     let monster_stats = build::stat::calc_stats(&monster_mods);
 
     let crit_multi = stats.val(StatId::CriticalStrikeMultiplier);
 
     let mut damage_instances = vec![];
     let mut bleed_dps = 0;
+    let mut ignite_dps = 0;
+    let ignite_chance = stats.val(StatId::ChanceToIgnite).clamp(0, 100);
+    // (dps of a single poison, chance to hit of the hits applying it)
+    let mut single_poison = (0, 0);
+    let poison_chance = stats.val(StatId::ChanceToPoison).clamp(0, 100);
+    // Strongest lightning and cold hits, after enemy resistances
+    let mut lightning_hit = 0;
+    let mut cold_hit = 0;
+
+    let mut impale_damage = 0;
 
     if tags.contains(GemTag::Attack) {
         let bleed_chance = stats.val(StatId::ChanceToBleed);
+        let impale_chance = stats.val(StatId::ChanceToImpale).clamp(0, 100);
+        let impales = build.property_int_stats(property::Int::ImpaleStacks, &stats);
+        if impale_chance > 0 {
+            ret.insert("Impale Chance", impale_chance);
+        }
 
         for slot in [Slot::Weapon, Slot::Offhand] {
             if let Some(weapon) = build.get_equipped(slot) {
@@ -313,6 +869,18 @@ pub fn calc_gem<'a>(build: &Build, support_gems: &[&Gem], active_gem: &Gem) -> F
 
                 let portions = apply_conversion(&stats, &base_damages);
                 let final_damages = apply_damage_mods_portions(&portions, &stats, item_class);
+                lightning_hit = lightning_hit.max(calc_ailment_hit(&stats, &monster_stats, &final_damages, DamageType::Lightning));
+                cold_hit = cold_hit.max(calc_ailment_hit(&stats, &monster_stats, &final_damages, DamageType::Cold));
+
+                if ignite_chance > 0 {
+                    ignite_dps = ignite_dps.max(calc_ignite_dps(&stats_ignite, &portions, item_class, crit_chance, (ignite_chance * chance_to_hit) / 100));
+                }
+                if poison_chance > 0 {
+                    let poison_dps = calc_single_poison_dps(&stats_poison, &portions, item_class, crit_chance);
+                    if poison_dps > single_poison.0 {
+                        single_poison = (poison_dps, chance_to_hit);
+                    }
+                }
 
                 let mut dmg_inst = DamageInstance {
                     source: DamageSource::Slot(slot),
@@ -325,6 +893,12 @@ pub fn calc_gem<'a>(build: &Build, support_gems: &[&Gem], active_gem: &Gem) -> F
                     if dg.damage_type == DamageType::Physical {
                         let pdr = physical_damage_reduction_armour(avg_damage, monster_stats.val(StatId::Armour), 0);
                         avg_damage = (avg_damage * (100 - pdr)) / 100;
+                        avg_damage = monster_stats.stat(StatId::PhysicalDamageTaken).val_custom(avg_damage);
+                        if impale_chance > 0 {
+                            impale_damage += (calc_impale_damage(&stats, avg_damage, crit_chance, crit_multi, impale_chance, impales) * chance_to_hit) / 100;
+                        }
+                    } else {
+                        avg_damage = apply_enemy_resistance(avg_damage, calc_enemy_resistance(&stats, &monster_stats, dg.damage_type, true));
                     }
 
                     dmg_inst.instance_type.push(DamageInstanceType {
@@ -363,6 +937,15 @@ pub fn calc_gem<'a>(build: &Build, support_gems: &[&Gem], active_gem: &Gem) -> F
 
         let portions = apply_conversion(&stats, &base_damages);
         let final_damages = apply_damage_mods_portions(&portions, &stats, None);
+        lightning_hit = calc_ailment_hit(&stats, &monster_stats, &final_damages, DamageType::Lightning);
+        cold_hit = calc_ailment_hit(&stats, &monster_stats, &final_damages, DamageType::Cold);
+
+        if ignite_chance > 0 {
+            ignite_dps = calc_ignite_dps(&stats_ignite, &portions, None, crit_chance, ignite_chance);
+        }
+        if poison_chance > 0 {
+            single_poison = (calc_single_poison_dps(&stats_poison, &portions, None, crit_chance), 100);
+        }
 
         let mut dmg_inst = DamageInstance {
             source: DamageSource::Gem,
@@ -370,27 +953,79 @@ pub fn calc_gem<'a>(build: &Build, support_gems: &[&Gem], active_gem: &Gem) -> F
         };
         for (i, dg) in DAMAGE_GROUPS.iter().enumerate() {
             if final_damages[i] > 0 {
+                let avg_damage = apply_enemy_resistance(final_damages[i], calc_enemy_resistance(&stats, &monster_stats, dg.damage_type, true));
                 dmg_inst.instance_type.push(DamageInstanceType {
                     typ: dg.damage_type,
-                    amount: final_damages[i],
+                    amount: avg_damage,
                     chance_to_hit: 100,
                     crit_chance,
                 });
-                damage.push(calc_dmg_crit_accuracy(final_damages[i], crit_chance, crit_multi, 100));
+                damage.push(calc_dmg_crit_accuracy(avg_damage, crit_chance, crit_multi, 100));
             }
         }
     }
 
+    let ailment_threshold = monster_stats.val(StatId::AilmentThreshold);
+    let shock_chance = stats.val(StatId::ChanceToShock).clamp(0, 100);
+    if shock_chance > 0 {
+        let shock_effect = calc_shock_effect(&stats, lightning_hit, ailment_threshold);
+        if shock_effect > 0 {
+            monster_mods.push(Mod { stat: StatId::DamageTaken, typ: Type::Inc, amount: calc_average_shock(shock_effect, shock_chance), source: Source::Custom("Shock"), ..Default::default() });
+            ret.insert("Shock Effect", shock_effect);
+            ret.insert("Shock Chance", shock_chance);
+            ret.insert("Shock Duration", stats.stat(StatId::ShockDuration).val_custom(SHOCK_BASE_DURATION));
+        }
+    }
+
+    let chill_effect = calc_chill_effect(&stats, cold_hit, ailment_threshold);
+    if chill_effect > 0 {
+        ret.insert("Chill Effect", chill_effect);
+        ret.insert("Chill Duration", stats.stat(StatId::ChillDuration).val_custom(CHILL_BASE_DURATION));
+    }
+
+    let freeze_chance = stats.val(StatId::ChanceToFreeze).clamp(0, 100);
+    if freeze_chance > 0 {
+        let freeze_duration = calc_freeze_duration(&stats, cold_hit, ailment_threshold);
+        if freeze_duration > 0 {
+            ret.insert("Freeze Chance", freeze_chance);
+            ret.insert("Freeze Duration", freeze_duration);
+        }
+    }
+
+    // Enemy side: everything the skill deals is scaled by the enemy's damage taken
+    let monster_stats = build::stat::calc_stats(&monster_mods);
+    let damage_taken = monster_stats.stat(StatId::DamageTaken);
+    let bleed_dps = damage_taken.val_custom(monster_stats.stat(StatId::PhysicalDamageTaken).val_custom(bleed_dps));
+    let ignite_dps = apply_enemy_resistance(damage_taken.val_custom(ignite_dps), calc_enemy_resistance(&stats_ignite, &monster_stats, DamageType::Fire, false));
+    single_poison.0 = apply_enemy_resistance(damage_taken.val_custom(single_poison.0), calc_enemy_resistance(&stats_poison, &monster_stats, DamageType::Chaos, false));
+
+    let dot_dps: i64 = DAMAGE_GROUPS.iter().map(|dg| {
+        let dps = calc_skill_dot_dps(&stats_dot, dg.damage_type);
+        apply_enemy_resistance(damage_taken.val_custom(dps), calc_enemy_resistance(&stats_dot, &monster_stats, dg.damage_type, false))
+    }).sum();
+    if dot_dps > 0 {
+        ret.insert("DoT DPS", dot_dps);
+    }
+
     ret.insert("Bleed DPS", bleed_dps);
 
+    if ignite_dps > 0 {
+        ret.insert("Ignite DPS", ignite_dps);
+        ret.insert("Ignite Chance", ignite_chance);
+        ret.insert("Ignite Duration", stats_ignite.stat(StatId::IgniteDuration).val_custom(IGNITE_BASE_DURATION));
+    }
+
     if ret.contains_key("Crit Chance") || ret.contains_key("Crit Chance (MH)") || ret.contains_key("Crit Chance (OH)") {
         ret.insert("Crit Multi", crit_multi);
     }
 
     let time = {
         if tags.contains(GemTag::Spell) {
-            if let Some(time) = active_gem.data().cast_time {
-                stats.stat(StatId::CastSpeed).val_custom_inv(time)
+            if let Some((time, cooldown)) = calc_trigger_time(build, warcries, &stats, support_gems, active_gem) {
+                ret.insert("Trigger Cooldown", cooldown);
+                time
+            } else if let Some(time) = active_gem.data().cast_time {
+                calc_action_time(&stats, StatId::CastSpeed, time)
             } else {
                 0
             }
@@ -411,7 +1046,7 @@ pub fn calc_gem<'a>(build: &Build, support_gems: &[&Gem], active_gem: &Gem) -> F
             if div > 0 {
                 time /= div;
                 time += stats.stat(StatId::AddedAttackTime).val();
-                stats.stat(StatId::AttackSpeed).val_custom_inv(time)
+                calc_action_time(&stats, StatId::AttackSpeed, time)
             } else {
                 0
             }
@@ -420,25 +1055,345 @@ pub fn calc_gem<'a>(build: &Build, support_gems: &[&Gem], active_gem: &Gem) -> F
         }
     };
 
+    // Totems each use the skill at its own rate, traps and mines once for each one thrown at a time
+    let (time, uses) = match deployer {
+        Some(Deployer::Totem) => {
+            ret.insert("Totem Placement Time", calc_action_time(&stats, StatId::TotemPlacementSpeed, TOTEM_PLACEMENT_TIME));
+            let totems = build.property_int_stats(property::Int::ActiveTotems, &stats);
+            ret.insert("Active Totems", totems);
+            (time, totems)
+        },
+        Some(Deployer::Trap) => {
+            let traps = build.property_int_stats(property::Int::ActiveTraps, &stats);
+            let thrown = calc_thrown_at_once(stats.val(StatId::AdditionalTraps), traps);
+            ret.insert("Active Traps", traps);
+            ret.insert("Traps per Throw", thrown);
+            (calc_action_time(&stats, StatId::TrapThrowingSpeed, TRAP_THROWING_TIME), thrown)
+        },
+        Some(Deployer::Mine) => {
+            let mines = build.property_int_stats(property::Int::ActiveMines, &stats);
+            let thrown = calc_thrown_at_once(stats.val(StatId::AdditionalMines), mines);
+            ret.insert("Active Mines", mines);
+            ret.insert("Mines per Throw", thrown);
+            (calc_action_time(&stats, StatId::MineThrowingSpeed, MINE_THROWING_TIME), thrown)
+        },
+        None => (time, 1),
+    };
+
     let mut mana_cost_stat = stats.stat(StatId::ManaCost).to_owned();
     mana_cost_stat.assimilate(stats.stat(StatId::Cost));
     ret.insert("Mana Cost", mana_cost_stat.val());
+    // Totems pay the cost once when placed rather than on each use
+    if time != 0 && deployer != Some(Deployer::Totem) {
+        let cost_per_second = (mana_cost_stat.val() * 1000) / time;
+        ret.insert("Mana Cost/s", cost_per_second);
+    }
+
+    if active_gem.data().active_skill.as_ref().is_some_and(|s| s.types.contains(&ActiveSkillType::CreatesMinion)) {
+        ret.extend(calc_minion(build, &mods, &stats, active_gem, &monster_stats));
+    }
 
-    let average_damage: i64 = damage.iter().sum();
+    let mut average_damage = damage_taken.val_custom(damage.iter().sum());
+    let exertable = active_gem.data().active_skill.as_ref().is_some_and(|s| !s.types.contains(&ActiveSkillType::NeverExertable) && !s.types.contains(&ActiveSkillType::Warcry));
+    if tags.contains(GemTag::Attack) && exertable && deployer.is_none() && time != 0 {
+        let exert_more_damage = calc_exert_more_damage(warcries, time);
+        if exert_more_damage > 0 {
+            ret.insert("Exert More Damage", exert_more_damage);
+            average_damage = (average_damage * (100 + exert_more_damage)) / 100;
+        }
+    }
+    let impale_damage = damage_taken.val_custom(impale_damage);
     ret.insert("Average Damage", average_damage);
 
     if time != 0 {
-        let dps = (average_damage * 1000) / time;
+        let impale_dps = (impale_damage * 1000 * uses) / time;
+        if impale_dps > 0 {
+            ret.insert("Impale DPS", impale_dps);
+        }
+        let dps = (average_damage * 1000 * uses) / time + impale_dps;
         ret.insert("DPS", dps);
         ret.insert("Speed", time);
+
+        if deployer.is_none() {
+            for (label, leech) in LEECH_LABELS.into_iter().zip(calc_leech(&stats, &damage_instances, crit_multi, damage_taken, time)) {
+                if leech > 0 {
+                    ret.insert(label, leech);
+                }
+            }
+        }
+
+        if single_poison.0 > 0 {
+            let duration = stats_poison.stat(StatId::PoisonDuration).val_custom(POISON_BASE_DURATION);
+            // Hundredths of a stack: hits per second of all deployers * chance to hit * chance to poison * duration
+            let stacks = (duration * single_poison.1 * poison_chance * 100 * uses) / (time * 10000);
+            ret.insert("Poison DPS", (single_poison.0 * stacks) / 100);
+            ret.insert("Poison Stacks", stacks);
+            ret.insert("Poison Duration", duration);
+        }
+    }
+
+    if let Some(cost_per_second) = ret.get("Mana Cost/s").copied() {
+        let recovery = calc_mana_regen(&stats, stats.stat(StatId::MaximumMana).val_ceil()) + ret.get("Mana Leech/s").copied().unwrap_or(0);
+        ret.insert("Net Mana/s", recovery - cost_per_second);
+    }
+    ret
+}
+
+#[derive(Default)]
+pub struct SkillOffence {
+    pub gem_link: usize,
+    /// Index among the active gems of the gem link
+    pub active_gem: usize,
+    pub name: &'static str,
+    pub dps: i64,
+    pub full_dps: bool,
+    pub uptime: i64,
+}
+
+#[derive(Default)]
+pub struct BuildOffence {
+    pub skills: Vec<SkillOffence>,
+    pub full_dps: i64,
+}
+
+/// DPS of a skill summed over hits, damage over time and ailments
+fn calc_skill_total_dps(calc: &FxHashMap<&'static str, i64>) -> i64 {
+    ["DPS", "DoT DPS", "Bleed DPS", "Ignite DPS", "Poison DPS", "Total Minion DPS"].iter().map(|k| calc.get(k).copied().unwrap_or(0)).sum()
+}
+
+/// Skill types of the gems dealing damage themselves, auras, curses, warcries and heralds have none of them
+const DAMAGE_SKILL_TYPES: [ActiveSkillType; 4] = [ActiveSkillType::Attack, ActiveSkillType::Damage, ActiveSkillType::DamageOverTime, ActiveSkillType::CreatesMinion];
+
+/// Evaluates every enabled active gem of the build dealing damage.
+/// The combined DPS only counts skills flagged for it, weighted by their uptime.
+pub fn calc_build_offence(build: &Build) -> BuildOffence {
+    let mut ret = BuildOffence::default();
+    let warcries = calc_warcry_uses(build);
+    for (link_idx, gem_link) in build.gem_links.iter().enumerate() {
+        let supports: Vec<&Gem> = gem_link.support_gems().filter(|g| g.enabled).map(|arc_gem| arc_gem.as_ref()).collect();
+        for (gem_idx, active_gem) in gem_link.active_gems().enumerate() {
+            let deals_damage = active_gem.data().active_skill.as_ref().is_some_and(|s| DAMAGE_SKILL_TYPES.iter().any(|t| s.types.contains(t)));
+            if !active_gem.enabled || !deals_damage {
+                continue;
+            }
+            let dps = calc_skill_total_dps(&calc_gem_with_warcries(build, &warcries, &supports, active_gem));
+            let uptime = active_gem.uptime.clamp(0, 100);
+            if active_gem.full_dps {
+                ret.full_dps += (dps * uptime) / 100;
+            }
+            ret.skills.push(SkillOffence {
+                gem_link: link_idx,
+                active_gem: gem_idx,
+                name: active_gem.data().display_name(),
+                dps,
+                full_dps: active_gem.full_dps,
+                uptime,
+            });
+        }
     }
     ret
 }
 
-pub fn calc_defence(build: &Build) -> (FxHashMap<&'static str, i64>, Stats) {
+/// Mana and life reserved by the enabled skills, each skill reserving once with its highest level gem.
+/// Reservations are scaled by the reservation efficiency of the skill and its supports, gems without
+/// reservation data for their level use the closest level that has it.
+/// Returns (reserved mana, reserved life).
+fn calc_reservation(build: &Build, mods: &[Mod], max_mana: i64, max_life: i64) -> (i64, i64) {
+    let mut best_gems: FxHashMap<&str, (&Gem, &build::GemLink)> = FxHashMap::default();
+    for link in &build.gem_links {
+        for active_gem in link.active_gems().filter(|gem| gem.enabled && gem.reservations().is_some()) {
+            if let Some((existing_gem, _)) = best_gems.get(active_gem.id.as_str()) {
+                if existing_gem.level >= active_gem.level {
+                    continue;
+                }
+            }
+            best_gems.insert(active_gem.id.as_str(), (active_gem, link));
+        }
+    }
+
+    let (mut mana, mut life) = (0, 0);
+    for (gem, link) in best_gems.values() {
+        let reservations = gem.reservations().unwrap();
+        let mut gem_mods = mods.to_vec();
+        gem_mods.extend_from_slice(&gem.calc_mods(false));
+        for support_gem in link.support_gems().filter(|g| g.enabled && g.can_support(gem)) {
+            gem_mods.extend_from_slice(&support_gem.calc_mods(false));
+        }
+        let tags = gem.data().tags.iter().copied().map(BitFlags::from).fold(BitFlags::empty(), |acc, flag| acc | flag);
+        let mut evaluator = Evaluator::new(build, &gem_mods, tags, make_bitflags!(ModFlag::{Aura | Buff}));
+
+        mana += calc_reserved(reservations.mana_flat.unwrap_or(0), reservations.mana_percent.unwrap_or(0.0), max_mana, evaluator.get_stat_mult(StatId::ManaReservationEfficiency));
+        life += calc_reserved(reservations.life_flat.unwrap_or(0), reservations.life_percent.unwrap_or(0.0), max_life, evaluator.get_stat_mult(StatId::LifeReservationEfficiency));
+    }
+    (mana, life)
+}
+
+/// Part of a pool reserved by a flat amount and a percentage of the pool, scaled by the
+/// reservation efficiency multiplier (10000 is unchanged) and rounded up
+fn calc_reserved(flat: i64, percent: f32, pool: i64, efficiency: i64) -> i64 {
+    // Hundredths of the pool
+    let base = flat * 100 + ((percent * 100.0) as i64 * pool) / 100;
+    (base * 10000 / efficiency + 99) / 100
+}
+
+/// Regeneration per second of a pool from flat and percentage regeneration, both in hundredths,
+/// scaled by every rate in `rates`
+fn calc_regen(stats: &Stats, pool: i64, flat: StatId, pct: StatId, rates: &[StatId]) -> i64 {
+    let regen = stats.val(flat) + (stats.val(pct) * pool) / 100;
+    rates.iter().fold(regen, |regen, rate| stats.stat(*rate).val_custom(regen)) / 100
+}
+
+/// Mana regenerated per second
+fn calc_mana_regen(stats: &Stats, max_mana: i64) -> i64 {
+    calc_regen(stats, max_mana, StatId::ManaRegeneration, StatId::ManaRegenerationPct, &[StatId::ManaRegenerationRate, StatId::ManaRecoveryRate])
+}
+
+const ENERGY_SHIELD_RECHARGE_DELAY: i64 = 2000;
+/// Hundredths of a percent of maximum energy shield recharged per second
+const ENERGY_SHIELD_RECHARGE_RATE: i64 = 3330;
+const WARD_RESTORATION_TIME: i64 = 4000;
+
+/// Total recovery per second of each pool, from the regeneration and recharge of the
+/// defence calc and the leech of the active skill. Recharge counts as if never interrupted.
+pub fn calc_recovery(defence: &FxHashMap<&'static str, i64>, skill: &FxHashMap<&'static str, i64>) -> FxHashMap<&'static str, i64> {
+    let sum = |defence_keys: &[&str], skill_key: &str| -> i64 {
+        defence_keys.iter().map(|k| defence.get(k).copied().unwrap_or(0)).sum::<i64>() + skill.get(skill_key).copied().unwrap_or(0)
+    };
+    let mut ret = FxHashMap::default();
+    ret.insert("Life Recovery/s", sum(&["Life Regeneration"], "Life Leech/s"));
+    ret.insert("Mana Recovery/s", sum(&["Mana Regeneration"], "Mana Leech/s"));
+    ret.insert("ES Recovery/s", sum(&["ES Regeneration", "ES Recharge"], "ES Leech/s"));
+    ret
+}
+
+const MAXIMUM_PHYSICAL_DAMAGE_REDUCTION: i64 = 90;
+const MAXIMUM_SPELL_SUPPRESSION: i64 = 100;
+const MAXIMUM_CHANCE_TO_EVADE: i64 = 95;
+
+/// Chances to block attacks, block spells and suppress spells, limited by their maximums
+fn calc_block_suppression(stats: &Stats) -> (i64, i64, i64) {
+    (
+        stats.val(StatId::ChanceToBlockAttackDamage).clamp(0, stats.val(StatId::MaximumChanceToBlockAttackDamage)),
+        stats.val(StatId::ChanceToBlockSpellDamage).clamp(0, stats.val(StatId::MaximumChanceToBlockSpellDamage)),
+        stats.val(StatId::ChanceToSuppressSpellDamage).clamp(0, MAXIMUM_SPELL_SUPPRESSION),
+    )
+}
+
+/// Average chance to evade the attack hits of a monster with the given accuracy
+fn calc_chance_to_evade(stats: &Stats, monster_accuracy: i64) -> i64 {
+    let evasion = stats.val(StatId::EvasionRating) as f32;
+    let accuracy = monster_accuracy as f32;
+    let chance_to_hit = ((((1.25 * accuracy) / (accuracy + (evasion * 0.2).powf(0.9))) * 100.0) as i64).clamp(5, 100);
+    let mut chance_to_evade = stats.stat(StatId::ChanceToEvade).to_owned();
+    chance_to_evade.adjust(Type::Base, 100 - chance_to_hit);
+    chance_to_evade.val().clamp(0, MAXIMUM_CHANCE_TO_EVADE)
+}
+
+/// Life and mana lost to a hit before dying, with part of the damage taken from mana first
+fn calc_life_mana_pool(life: i64, mana: i64, mana_before_life: i64) -> i64 {
+    match mana_before_life {
+        ..=0 => life,
+        100.. => life + mana,
+        pct => ((life * 100) / (100 - pct)).min(life + mana),
+    }
+}
+
+/// Damage taken stat of each damage type, indexed like `DamageType::as_index`
+const DAMAGE_TAKEN_STATS: [StatId; 5] = [
+    StatId::PhysicalDamageTaken,
+    StatId::FireDamageTaken,
+    StatId::ColdDamageTaken,
+    StatId::LightningDamageTaken,
+    StatId::ChaosDamageTaken,
+];
+
+/// Largest hit of each damage type survived from full unreserved pools, indexed like `DamageType::as_index`.
+/// `hit_damage_taken` holds the damage taken from hits of each damage type, generic included.
+/// A single hit isn't blocked or suppressed, see `calc_spell_max_hit`. None if the damage type can't hurt.
+fn calc_max_hits(stats: &Stats, hit_damage_taken: &[Stat; 5], life: i64, mana: i64) -> [Option<i64>; 5] {
+    let life_mana_pool = calc_life_mana_pool(life, mana, stats.val(StatId::DamageTakenFromManaBeforeLife));
+    let energy_shield = stats.val(StatId::MaximumEnergyShield);
+
+    DAMAGE_GROUPS.map(|dg| {
+        // Chaos damage bypasses energy shield
+        let pool = match dg.damage_type {
+            DamageType::Chaos => life_mana_pool,
+            _ => life_mana_pool + energy_shield,
+        };
+        let damage_taken_mult = hit_damage_taken[dg.damage_type.as_index()].mult();
+        let resistance = match dg.damage_type {
+            DamageType::Physical => None,
+            DamageType::Fire => Some((StatId::FireResistance, StatId::MaximumFireResistance)),
+            DamageType::Cold => Some((StatId::ColdResistance, StatId::MaximumColdResistance)),
+            DamageType::Lightning => Some((StatId::LightningResistance, StatId::MaximumLightningResistance)),
+            DamageType::Chaos => Some((StatId::ChaosResistance, StatId::MaximumChaosResistance)),
+        };
+
+        match resistance {
+            Some((res, max_res)) => calc_max_hit(pool, damage_taken_mult, 100 - stats.val(res).min(stats.val(max_res))),
+            None => calc_max_physical_hit(pool, damage_taken_mult, stats.val(StatId::Armour), stats.val(StatId::PhysicalDamageReduction)),
+        }
+    })
+}
+
+/// Largest hit survived with `pool`, given the damage taken multiplier (10000 is unchanged)
+/// and the percentage of the hit left after mitigation. None if no hit can hurt.
+fn calc_max_hit(pool: i64, damage_taken_mult: i64, mitigated_taken: i64) -> Option<i64> {
+    if damage_taken_mult <= 0 || mitigated_taken <= 0 {
+        return None;
+    }
+    Some((((pool * 10000) / damage_taken_mult) * 100) / mitigated_taken)
+}
+
+/// Largest physical hit survived with `pool`, with armour evaluated against that same hit.
+/// Solves `hit * (1 - pdr - armour / (armour + 5 * hit)) * mult = pool` for the hit,
+/// unless the reduction reaches its maximum.
+fn calc_max_physical_hit(pool: i64, damage_taken_mult: i64, armour: i64, pdr: i64) -> Option<i64> {
+    let capped = calc_max_hit(pool, damage_taken_mult, 100 - MAXIMUM_PHYSICAL_DAMAGE_REDUCTION)?;
+    if physical_damage_reduction_armour(capped.max(1), armour.max(0), pdr) >= MAXIMUM_PHYSICAL_DAMAGE_REDUCTION {
+        return Some(capped);
+    }
+    let (pool, mult, armour, pdr) = (pool as f64, damage_taken_mult as f64 / 10000.0, armour.max(0) as f64, pdr as f64 / 100.0);
+    let a = 5.0 * mult * (1.0 - pdr);
+    let b = mult * pdr * armour + 5.0 * pool;
+    let c = pool * armour;
+    Some(((b + (b * b + 4.0 * a * c).sqrt()) / (2.0 * a)) as i64)
+}
+
+/// Largest spell hit survived on average, as blocked spells deal no damage and suppressed
+/// ones have part of their damage prevented. None if every spell is blocked.
+fn calc_spell_max_hit(max_hit: Option<i64>, spell_block: i64, suppression: i64, suppressed_prevented: i64) -> Option<i64> {
+    // Hundredths of a percent of the damage taken
+    let taken = ((100 - spell_block) * (10000 - suppression * suppressed_prevented.clamp(0, 100))) / 100;
+    if taken <= 0 {
+        return None;
+    }
+    max_hit.map(|max_hit| (max_hit * 10000) / taken)
+}
+
+/// Warnings about reservation and skill costs the build can't afford
+pub fn calc_resource_warnings(defence: &FxHashMap<&'static str, i64>, skill: &FxHashMap<&'static str, i64>) -> Vec<&'static str> {
+    let mut ret = vec![];
+    if defence.get("Unreserved Mana").is_some_and(|v| *v < 0) {
+        ret.push("Skills reserve more mana than available");
+    }
+    if defence.get("Unreserved Life").is_some_and(|v| *v <= 0) {
+        ret.push("Skills reserve all of your life");
+    }
+    let available_mana = defence.get("Unreserved Mana").or(defence.get("Maximum Mana")).copied().unwrap_or(0);
+    if skill.get("Mana Cost").is_some_and(|cost| *cost > available_mana) {
+        ret.push("Not enough unreserved mana to use the skill");
+    } else if skill.get("Net Mana/s").is_some_and(|v| *v < 0) {
+        ret.push("Mana recovery can't sustain the skill");
+    }
+    ret
+}
+
+/// `leeching` enables the mods applying while leeching, from the skill in use (see `is_leeching`)
+pub fn calc_defence(build: &Build, leeching: bool) -> (FxHashMap<&'static str, i64>, Stats) {
     let mut ret = FxHashMap::default();
     let mods = build.calc_mods(true);
-    let stats = build.calc_stats(&mods, BitFlags::EMPTY, make_bitflags!(ModFlag::{Aura | Buff}));
+    let mut stats = build.calc_stats_leeching(&mods, BitFlags::EMPTY, make_bitflags!(ModFlag::{Aura | Buff}), leeching);
 
     let max_life = stats.stat(StatId::MaximumLife).val_ceil();
     let max_mana = stats.stat(StatId::MaximumMana).val_ceil();
@@ -458,23 +1413,99 @@ pub fn calc_defence(build: &Build) -> (FxHashMap<&'static str, i64>, Stats) {
     ret.insert("Armour", stats.val(StatId::Armour));
     ret.insert("Evasion", stats.val(StatId::EvasionRating));
     ret.insert("Energy Shield", stats.val(StatId::MaximumEnergyShield));
-    ret.insert("Spell Suppression", stats.val(StatId::ChanceToSuppressSpellDamage));
-    ret.insert("Block", stats.val(StatId::ChanceToBlockAttackDamage));
-    ret.insert("Spell Block", stats.val(StatId::ChanceToBlockSpellDamage));
 
-    let mut life_regen = stats.stat(StatId::LifeRegeneration).to_owned();
-    life_regen.adjust(Type::Base, (stats.stat(StatId::LifeRegenerationPct).val() * max_life) / 100);
-    life_regen.adjust(Type::More, stats.stat(StatId::LifeRegenerationRate).val());
-    ret.insert("Life Regeneration", life_regen.val() / 100);
+    let (block, spell_block, suppression) = calc_block_suppression(&stats);
+    ret.insert("Spell Suppression", suppression);
+    ret.insert("Block", block);
+    ret.insert("Spell Block", spell_block);
+
+    let monster_level = build.property_int(property::Int::Level).min(83);
+    let map_stats = build::stat::calc_stats(&build.calc_map_mods(true));
+    let monster_accuracy = map_stats.stat(StatId::AccuracyRating).val_custom(MONSTER_STATS.get(&monster_level).map_or(0, |m| m.accuracy));
+    let chance_to_evade = calc_chance_to_evade(&stats, monster_accuracy);
+    ret.insert("Chance to Evade", chance_to_evade);
+    let avoid_hits = stats.val(StatId::ChanceToAvoidDamageFromHits).clamp(0, 100);
+    ret.insert("Chance to Avoid Attacks", 100 - ((100 - chance_to_evade) * (100 - block) * (100 - avoid_hits)) / 10000);
+    ret.insert("Chance to Avoid Spells", 100 - ((100 - spell_block) * (100 - avoid_hits)) / 100);
+    ret.insert("Movement Speed", stats.stat(StatId::MovementSpeed).val_custom(stats.stat(StatId::ActionSpeed).val_custom(100)));
+
+    ret.insert("Life Regeneration", calc_regen(&stats, max_life, StatId::LifeRegeneration, StatId::LifeRegenerationPct, &[StatId::LifeRegenerationRate, StatId::LifeRecoveryRate]));
+    ret.insert("Mana Regeneration", calc_mana_regen(&stats, max_mana));
+
+    let max_es = stats.val(StatId::MaximumEnergyShield);
+    ret.insert("ES Regeneration", calc_regen(&stats, max_es, StatId::EnergyShieldRegeneration, StatId::EnergyShieldRegenerationPct, &[StatId::EnergyShieldRecoveryRate]));
+    if max_es > 0 {
+        let recharge = stats.stat(StatId::EnergyShieldRechargeRate).val_custom((max_es * ENERGY_SHIELD_RECHARGE_RATE) / 10000);
+        ret.insert("ES Recharge", stats.stat(StatId::EnergyShieldRecoveryRate).val_custom(recharge));
+        ret.insert("ES Recharge Delay", stats.stat(StatId::EnergyShieldRechargeStart).val_custom_inv(ENERGY_SHIELD_RECHARGE_DELAY));
+    }
+
+    let ward = stats.val(StatId::Ward);
+    if ward > 0 {
+        ret.insert("Ward", ward);
+        ret.insert("Ward Restoration Time", stats.stat(StatId::WardRestoration).val_custom_inv(WARD_RESTORATION_TIME));
+    }
 
-    let mut mana_regen = stats.stat(StatId::ManaRegeneration).to_owned();
-    mana_regen.adjust(Type::Base, (stats.stat(StatId::ManaRegenerationPct).val() * max_mana) / 10000);
-    mana_regen.adjust(Type::More, (stats.stat(StatId::ManaRegenerationRate).val() * max_mana) / 10000);
-    ret.insert("Mana Regeneration", mana_regen.val());
+    let (reserved_mana, reserved_life) = calc_reservation(build, &mods, max_mana, max_life);
+    if reserved_mana > 0 {
+        ret.insert("Reserved Mana", reserved_mana);
+        ret.insert("Unreserved Mana", max_mana - reserved_mana);
+    }
+    if reserved_life > 0 {
+        ret.insert("Reserved Life", reserved_life);
+        ret.insert("Unreserved Life", max_life - reserved_life);
+    }
+
+    // Fortification only reduces the damage taken from hits
+    let mut evaluator = Evaluator::new(build, &mods, BitFlags::EMPTY, make_bitflags!(ModFlag::{Hit | Aura | Buff})).with_leeching(leeching);
+    let generic_damage_taken = evaluator.eval_stat(StatId::DamageTaken).clone();
+    let hit_damage_taken = DAMAGE_TAKEN_STATS.map(|stat_id| {
+        let mut damage_taken = generic_damage_taken.clone();
+        damage_taken.assimilate(evaluator.eval_stat(stat_id));
+        damage_taken
+    });
+    // Armour is evaluated against each max hit itself
+    let max_hits = calc_max_hits(&stats, &hit_damage_taken, max_life - reserved_life, max_mana - reserved_mana);
+    let suppressed_prevented = stats.val(StatId::SuppressedSpellDamagePrevented);
+    for (i, (label, spell_label)) in [
+        ("Max Physical Hit", "Max Physical Spell Hit"),
+        ("Max Fire Hit", "Max Fire Spell Hit"),
+        ("Max Cold Hit", "Max Cold Spell Hit"),
+        ("Max Lightning Hit", "Max Lightning Spell Hit"),
+        ("Max Chaos Hit", "Max Chaos Spell Hit"),
+    ].into_iter().enumerate() {
+        if let Some(max_hit) = max_hits[i] {
+            ret.insert(label, max_hit);
+        }
+        if let Some(max_hit) = calc_spell_max_hit(max_hits[i], spell_block, suppression, suppressed_prevented) {
+            ret.insert(spell_label, max_hit);
+        }
+    }
+
+    // Map mods scale the overridden hit size as well
+    let hit_size = map_stats.stat(StatId::Damage).val_custom(build.enemy.hit_size(monster_level));
+    let pdr_before_armour = stats.val(StatId::PhysicalDamageReduction);
+    let pdr = physical_damage_reduction_armour(hit_size.max(1), stats.val(StatId::Armour), pdr_before_armour).min(MAXIMUM_PHYSICAL_DAMAGE_REDUCTION);
+    ret.insert("Enemy Hit Size", hit_size);
+    ret.insert("Physical Damage Reduction", pdr);
+    // Armour against the enemy hit size counts as physical damage reduction from here on
+    stats.stats.entry(StatId::PhysicalDamageReduction).or_default().adjust_mod_move(Mod {
+        stat: StatId::PhysicalDamageReduction,
+        typ: Type::Base,
+        amount: pdr - pdr_before_armour,
+        source: Source::Custom("Armour"),
+        ..Default::default()
+    });
 
     (ret, stats)
 }
 
+/// Derivation of a single defensive stat, evaluated the same way as `calc_defence`
+pub fn trace_defence_stat(build: &Build, stat_id: StatId, leeching: bool) -> StatTrace {
+    let mods = build.calc_mods(true);
+    build.trace_stat(stat_id, &mods, BitFlags::EMPTY, make_bitflags!(ModFlag::{Aura | Buff}), leeching)
+}
+
 #[derive(Debug)]
 pub struct PowerReport {
     pub nodes_delta: FxHashMap<u32, f32>,
@@ -482,8 +1513,8 @@ pub struct PowerReport {
 }
 
 impl PowerReport {
-    pub fn new_defence(build: &Build, delta_str: &str) -> PowerReport {
-        let defence = calc_defence(build).0;
+    pub fn new_defence(build: &Build, delta_str: &str, leeching: bool) -> PowerReport {
+        let defence = calc_defence(build, leeching).0;
 
         let nodes_compare: Vec<u32> = build.tree.nodes_data.keys()
             .filter(|node_id| !build.tree.nodes.contains(node_id))
@@ -497,7 +1528,7 @@ impl PowerReport {
                 local_build.tree.nodes.push(*node_id);
                 local_build.tree.invalidate_modcache();
 
-                let calc = calc_defence(&*local_build).0;
+                let calc = calc_defence(&*local_build, leeching).0;
                 let delta = *calc.get(delta_str).unwrap_or(&0) as f32 / *defence.get(delta_str).unwrap_or(&0) as f32;
 
                 local_build.tree.nodes.pop();
@@ -542,3 +1573,367 @@ impl PowerReport {
         }
     }
 }
+
+#[cfg(test)]
+fn test_stats(mods: &[(StatId, Type, i64)]) -> Stats {
+    let mods: Vec<Mod> = mods.iter().map(|(stat, typ, amount)| Mod { stat: *stat, typ: *typ, amount: *amount, ..Default::default() }).collect();
+    build::stat::calc_stats(&mods)
+}
+
+#[cfg(test)]
+fn test_portions(dt: DamageType, amount: i64) -> [Vec<DamagePortion>; 5] {
+    let mut portions: [Vec<DamagePortion>; 5] = Default::default();
+    portions[dt.as_index()].push(DamagePortion { amount, source_types: dt.into() });
+    portions
+}
+
+#[test]
+fn test_ignite_dps() {
+    // 90% of the hit, with 50% increased damage and +20% fire dot multiplier
+    let stats = test_stats(&[(StatId::Damage, Type::Inc, 50), (StatId::FireDotMultiplier, Type::Base, 20)]);
+    assert_eq!(calc_ignite_dps(&stats, &test_portions(DamageType::Fire, 1000), None, 0, 100), 1620);
+    // Scaled by the chance to ignite
+    assert_eq!(calc_ignite_dps(&stats, &test_portions(DamageType::Fire, 1000), None, 0, 25), 405);
+
+    // Crits are averaged in: 50% chance of 150% damage
+    let stats = test_stats(&[(StatId::CriticalStrikeMultiplier, Type::Base, 150)]);
+    assert_eq!(calc_ignite_dps(&stats, &test_portions(DamageType::Fire, 1000), None, 5000, 100), 1125);
+
+    assert_eq!(calc_ignite_dps(&stats, &test_portions(DamageType::Lightning, 1000), None, 0, 100), 0);
+}
+
+#[test]
+fn test_single_poison_dps() {
+    // 30% of the physical and chaos damage of the hit
+    let stats = test_stats(&[]);
+    assert_eq!(calc_single_poison_dps(&stats, &test_portions(DamageType::Chaos, 1000), None, 0), 300);
+    assert_eq!(calc_single_poison_dps(&stats, &test_portions(DamageType::Physical, 1000), None, 0), 300);
+    assert_eq!(calc_single_poison_dps(&stats, &test_portions(DamageType::Fire, 1000), None, 0), 0);
+
+    let stats = test_stats(&[(StatId::ChaosDamage, Type::Inc, 100), (StatId::ChaosDotMultiplier, Type::Base, 50)]);
+    assert_eq!(calc_single_poison_dps(&stats, &test_portions(DamageType::Chaos, 1000), None, 0), 900);
+}
+
+#[test]
+fn test_armour_pdr() {
+    // Armour counts for less against bigger hits
+    assert_eq!(physical_damage_reduction_armour(1000, 5000, 0), 50);
+    assert_eq!(physical_damage_reduction_armour(4000, 5000, 0), 20);
+    assert_eq!(physical_damage_reduction_armour(1000, 5000, 10), 60);
+    assert_eq!(physical_damage_reduction_armour(1000, 0, 0), 0);
+
+    // The configured enemy hit is the one compared against the armour
+    let mut player = Build::new_player();
+    player.enemy.set_override(build::enemy::EnemyValue::HitSize, 1234);
+    let (defence, stats) = calc_defence(&player, false);
+    assert_eq!(defence["Enemy Hit Size"], 1234);
+    assert_eq!(defence["Physical Damage Reduction"], physical_damage_reduction_armour(1234, stats.val(StatId::Armour), 0));
+}
+
+#[test]
+fn test_thrown_at_once() {
+    assert_eq!(calc_thrown_at_once(0, 15), 1);
+    assert_eq!(calc_thrown_at_once(3, 15), 4);
+    assert_eq!(calc_thrown_at_once(3, 2), 2);
+    assert_eq!(calc_thrown_at_once(-1, 15), 1);
+}
+
+#[test]
+fn test_build_offence() {
+    let calc: FxHashMap<&'static str, i64> = [("DPS", 1000), ("Ignite DPS", 200), ("Poison DPS", 50), ("Speed", 800)].into_iter().collect();
+    assert_eq!(calc_skill_total_dps(&calc), 1250);
+
+    // No damaging skill, nothing to combine
+    let offence = calc_build_offence(&Build::new_player());
+    assert!(offence.skills.is_empty());
+    assert_eq!(offence.full_dps, 0);
+}
+
+#[test]
+fn test_skill_dot_dps() {
+    // Base damage over time is per minute
+    let stats = test_stats(&[(StatId::BaseFireDamageOverTime, Type::Base, 6000)]);
+    assert_eq!(calc_skill_dot_dps(&stats, DamageType::Fire), 100);
+    assert_eq!(calc_skill_dot_dps(&stats, DamageType::Cold), 0);
+
+    let stats = test_stats(&[
+        (StatId::BaseFireDamageOverTime, Type::Base, 6000),
+        (StatId::FireDamage, Type::Inc, 50),
+        (StatId::FireDotMultiplier, Type::Base, 20),
+    ]);
+    assert_eq!(calc_skill_dot_dps(&stats, DamageType::Fire), 180);
+}
+
+#[test]
+fn test_impale_damage() {
+    // Each impale stores 10% of the physical hit, crit chance in hundredths of a percent
+    let stats = test_stats(&[]);
+    assert_eq!(calc_impale_damage(&stats, 1000, 0, 150, 100, 5), 500);
+    assert_eq!(calc_impale_damage(&stats, 1000, 10000, 200, 100, 5), 1000);
+    assert_eq!(calc_impale_damage(&stats, 1000, 0, 150, 0, 5), 0);
+
+    let stats = test_stats(&[(StatId::ImpaleEffect, Type::Inc, 50)]);
+    assert_eq!(calc_impale_damage(&stats, 1000, 0, 150, 100, 5), 750);
+    assert_eq!(calc_impale_damage(&stats, 1000, 0, 150, 50, 5), 375);
+}
+
+#[test]
+fn test_ailment_effects() {
+    assert_eq!(calc_ailment_base_effect(1000, 1000), 50);
+    assert_eq!(calc_ailment_base_effect(100, 1000), 19);
+    assert_eq!(calc_ailment_base_effect(0, 1000), 0);
+    assert_eq!(calc_ailment_base_effect(1000, 0), 0);
+
+    let stats = test_stats(&[]);
+    // Shocks are capped at 50% and chills at 30%, weaker ones than 5% don't apply
+    assert_eq!(calc_shock_effect(&stats, 10000, 1000), 50);
+    assert_eq!(calc_shock_effect(&stats, 1, 1000), 0);
+    assert_eq!(calc_chill_effect(&stats, 1000, 1000), 30);
+    assert_eq!(calc_chill_effect(&stats, 1, 1000), 0);
+
+    let stats = test_stats(&[(StatId::ShockEffect, Type::Inc, 50)]);
+    assert_eq!(calc_shock_effect(&stats, 100, 1000), 28);
+    // A low chance to shock only rarely applies the effect
+    assert_eq!(calc_average_shock(50, 100), 50);
+    assert_eq!(calc_average_shock(50, 10), 5);
+    assert_eq!(calc_average_shock(50, 1), 0);
+
+    // Sized by the hit after the enemy's resistance
+    let monster_stats = test_stats(&[(StatId::LightningResistance, Type::Base, 50)]);
+    assert_eq!(calc_ailment_hit(&test_stats(&[]), &monster_stats, &[0, 0, 0, 1000, 0], DamageType::Lightning), 500);
+
+    // 60ms per percent of the ailment threshold, between 0.3s and 3s
+    let stats = test_stats(&[]);
+    assert_eq!(calc_freeze_duration(&stats, 1000, 1000), 0);
+    assert_eq!(calc_freeze_duration(&stats, 10000, 1000), 600);
+    assert_eq!(calc_freeze_duration(&stats, 100000, 1000), FREEZE_MAX_DURATION);
+}
+
+#[test]
+fn test_enemy_resistance() {
+    let monster_stats = test_stats(&[(StatId::FireResistance, Type::Base, 40), (StatId::ChaosResistance, Type::Base, 20)]);
+    // Exposure applies to everything, penetration (stored x100) only to hits
+    let stats = test_stats(&[(StatId::FireExposure, Type::Base, 10), (StatId::FireDamagePen, Type::Base, 1500)]);
+    assert_eq!(calc_enemy_resistance(&stats, &monster_stats, DamageType::Fire, false), 30);
+    assert_eq!(calc_enemy_resistance(&stats, &monster_stats, DamageType::Fire, true), 15);
+    assert_eq!(calc_enemy_resistance(&stats, &monster_stats, DamageType::Chaos, true), 20);
+    assert_eq!(calc_enemy_resistance(&stats, &monster_stats, DamageType::Physical, true), 0);
+
+    // Capped at 75% after exposure, before penetration
+    let monster_stats = test_stats(&[(StatId::FireResistance, Type::Base, 100)]);
+    assert_eq!(calc_enemy_resistance(&stats, &monster_stats, DamageType::Fire, false), MONSTER_MAX_RESISTANCE);
+    assert_eq!(calc_enemy_resistance(&stats, &monster_stats, DamageType::Fire, true), MONSTER_MAX_RESISTANCE - 15);
+
+    // Only the strongest exposure applies
+    let stats = test_stats(&[(StatId::FireExposure, Type::Base, 10), (StatId::FireExposure, Type::Base, 12)]);
+    let monster_stats = test_stats(&[(StatId::FireResistance, Type::Base, 40)]);
+    assert_eq!(calc_enemy_resistance(&stats, &monster_stats, DamageType::Fire, false), 28);
+
+    assert_eq!(apply_enemy_resistance(1000, 75), 250);
+    assert_eq!(apply_enemy_resistance(1000, -20), 1200);
+}
+
+#[test]
+fn test_trigger_time() {
+    assert_eq!(round_server_tick(0), 0);
+    assert_eq!(round_server_tick(1), 33);
+    assert_eq!(round_server_tick(100), 100);
+    assert_eq!(round_server_tick(150), 166);
+
+    // Crit chance in hundredths of a percent
+    assert_eq!(calc_crit_trigger_time(1000, 5000, 100), 2000);
+    assert_eq!(calc_crit_trigger_time(1000, 5000, 50), 4000);
+    // Never triggers without crits or attacks
+    assert_eq!(calc_crit_trigger_time(1000, 0, 100), 0);
+    assert_eq!(calc_crit_trigger_time(1000, 5000, 0), 0);
+    assert_eq!(calc_crit_trigger_time(0, 5000, 100), 0);
+
+    assert_eq!(item_mod_trigger("Socketed Gems are Supported by Level 20 Cast On Critical Strike"), Some(Some(Trigger::CastOnCrit)));
+    assert_eq!(item_mod_trigger("Socketed Gems are Supported by Level 18 Cast when Damage Taken"), Some(None));
+    assert_eq!(item_mod_trigger("Trigger Level 20 Bone Nova when you Kill an Enemy"), Some(None));
+    assert_eq!(item_mod_trigger("Socketed Gems are Supported by Level 20 Added Fire Damage"), None);
+    assert_eq!(item_mod_trigger("+50 to maximum Life"), None);
+}
+
+#[test]
+fn test_leech() {
+    let hit = |amount| DamageInstance {
+        source: DamageSource::Gem,
+        instance_type: vec![DamageInstanceType { typ: DamageType::Physical, amount, chance_to_hit: 100, crit_chance: 0 }],
+    };
+    // 2% of the damage as life, leech stored x100
+    let stats = test_stats(&[
+        (StatId::MaximumLife, Type::Base, 1000),
+        (StatId::LifeLeech, Type::Base, 200),
+        (StatId::MaximumLifeLeechRate, Type::Base, 20),
+    ]);
+    assert_eq!(calc_leech(&stats, &[hit(1000)], 150, &Stat::default(), 500), [40, 0, 0]);
+    // Limited to 10% of the pool per hit, and to the maximum leech rate per second
+    assert_eq!(calc_leech(&stats, &[hit(100000)], 150, &Stat::default(), 1000), [100, 0, 0]);
+    assert_eq!(calc_leech(&stats, &[hit(1000)], 150, &Stat::default(), 50), [200, 0, 0]);
+
+    // Per damage type leech only applies to its damage type
+    let stats = test_stats(&[
+        (StatId::MaximumLife, Type::Base, 1000),
+        (StatId::FireLifeLeech, Type::Base, 200),
+        (StatId::MaximumLifeLeechRate, Type::Base, 20),
+    ]);
+    assert_eq!(calc_leech(&stats, &[hit(1000)], 150, &Stat::default(), 500), [0, 0, 0]);
+}
+
+#[test]
+fn test_reservation() {
+    assert_eq!(calc_reserved(0, 50.0, 1000, 10000), 500);
+    assert_eq!(calc_reserved(30, 0.0, 1000, 10000), 30);
+    assert_eq!(calc_reserved(0, 25.0, 1001, 10000), 251);
+    // 100% increased efficiency halves the reservation
+    assert_eq!(calc_reserved(0, 50.0, 1000, 20000), 250);
+    assert_eq!(calc_reserved(0, 0.0, 1000, 10000), 0);
+
+    let mods = crate::modifier::parse_mod("25% increased reservation efficiency of skills", Source::Innate).unwrap();
+    let life_efficiency = build::stat::calc_stats(&mods).stat(StatId::LifeReservationEfficiency).mult();
+    assert_eq!(calc_reserved(0, 50.0, 1000, life_efficiency), 400);
+}
+
+#[test]
+fn test_resource_warnings() {
+    let map = |entries: &[(&'static str, i64)]| entries.iter().copied().collect::<FxHashMap<_, _>>();
+    assert!(calc_resource_warnings(&map(&[("Unreserved Mana", 100)]), &map(&[("Mana Cost", 10)])).is_empty());
+    assert_eq!(calc_resource_warnings(&map(&[("Unreserved Mana", -5)]), &map(&[])), ["Skills reserve more mana than available"]);
+    assert_eq!(calc_resource_warnings(&map(&[("Unreserved Life", 0)]), &map(&[])), ["Skills reserve all of your life"]);
+    assert_eq!(calc_resource_warnings(&map(&[("Maximum Mana", 50)]), &map(&[("Mana Cost", 60)])), ["Not enough unreserved mana to use the skill"]);
+    assert_eq!(
+        calc_resource_warnings(&map(&[("Maximum Mana", 50)]), &map(&[("Mana Cost", 10), ("Net Mana/s", -1)])),
+        ["Mana recovery can't sustain the skill"]
+    );
+}
+
+#[test]
+fn test_max_hit() {
+    assert_eq!(calc_max_hit(1000, 10000, 100), Some(1000));
+    assert_eq!(calc_max_hit(1000, 10000, 25), Some(4000));
+    assert_eq!(calc_max_hit(1000, 5000, 100), Some(2000));
+    assert_eq!(calc_max_hit(1000, 10000, 0), None);
+    assert_eq!(calc_max_hit(1000, 0, 100), None);
+
+    assert_eq!(calc_life_mana_pool(1000, 500, 0), 1000);
+    assert_eq!(calc_life_mana_pool(1000, 500, 100), 1500);
+    assert_eq!(calc_life_mana_pool(1000, 500, 20), 1250);
+    // Limited by the mana available
+    assert_eq!(calc_life_mana_pool(1000, 100, 50), 1100);
+
+    // 10000 armour against a 2000 hit is 50% reduction
+    assert_eq!(calc_max_physical_hit(1000, 10000, 10000, 0), Some(2000));
+    assert_eq!(calc_max_physical_hit(1000, 10000, 0, 20), Some(1250));
+    // High armour reaches the maximum reduction even against the max hit
+    assert_eq!(calc_max_physical_hit(1000, 10000, 1_000_000, 0), Some(10000));
+
+    // Spell block and suppression, half of the suppressed damage prevented
+    assert_eq!(calc_spell_max_hit(Some(1000), 0, 0, 50), Some(1000));
+    assert_eq!(calc_spell_max_hit(Some(1000), 50, 100, 50), Some(4000));
+    assert_eq!(calc_spell_max_hit(Some(1000), 100, 0, 50), None);
+
+    let stats = test_stats(&[
+        (StatId::MaximumEnergyShield, Type::Base, 500),
+        (StatId::FireResistance, Type::Base, 90),
+        (StatId::MaximumFireResistance, Type::Base, 75),
+        (StatId::PhysicalDamageReduction, Type::Base, 95),
+    ]);
+    let mut hit_damage_taken: [Stat; 5] = Default::default();
+    hit_damage_taken[DamageType::Cold.as_index()].adjust(Type::Inc, -50);
+    let max_hits = calc_max_hits(&stats, &hit_damage_taken, 1000, 0);
+    // Resistance and physical damage reduction are capped
+    assert_eq!(max_hits[DamageType::Fire.as_index()], Some(6000));
+    assert_eq!(max_hits[DamageType::Physical.as_index()], Some(15000));
+    assert_eq!(max_hits[DamageType::Cold.as_index()], Some(3000));
+    // Chaos damage bypasses energy shield
+    assert_eq!(max_hits[DamageType::Chaos.as_index()], Some(1000));
+}
+
+#[test]
+fn test_evade_block() {
+    assert_eq!(calc_chance_to_evade(&test_stats(&[]), 1000), 0);
+    assert_eq!(calc_chance_to_evade(&test_stats(&[(StatId::EvasionRating, Type::Base, 1_000_000)]), 1000), MAXIMUM_CHANCE_TO_EVADE);
+    assert_eq!(calc_chance_to_evade(&test_stats(&[(StatId::ChanceToEvade, Type::Base, 200)]), 1000), MAXIMUM_CHANCE_TO_EVADE);
+
+    let stats = test_stats(&[
+        (StatId::ChanceToBlockAttackDamage, Type::Base, 100),
+        (StatId::MaximumChanceToBlockAttackDamage, Type::Base, 100),
+        (StatId::ChanceToBlockSpellDamage, Type::Base, 100),
+        (StatId::MaximumChanceToBlockSpellDamage, Type::Base, 75),
+        (StatId::ChanceToSuppressSpellDamage, Type::Base, 120),
+    ]);
+    assert_eq!(calc_block_suppression(&stats), (100, 75, 100));
+    assert_eq!(calc_block_suppression(&test_stats(&[])), (0, 0, 0));
+}
+
+#[test]
+fn test_recovery() {
+    let regen = |stats: &Stats| calc_regen(stats, 1000, StatId::LifeRegeneration, StatId::LifeRegenerationPct, &[StatId::LifeRecoveryRate]);
+    // 2% of 1000 life per second
+    assert_eq!(regen(&test_stats(&[(StatId::LifeRegenerationPct, Type::Base, 200)])), 20);
+    assert_eq!(
+        regen(&test_stats(&[
+            (StatId::LifeRegenerationPct, Type::Base, 200),
+            (StatId::LifeRegeneration, Type::Base, 500),
+            (StatId::LifeRecoveryRate, Type::Inc, 50),
+        ])),
+        37
+    );
+
+    let defence = [("Life Regeneration", 20), ("ES Regeneration", 5), ("ES Recharge", 100)].into_iter().collect();
+    let skill = [("Life Leech/s", 30)].into_iter().collect();
+    let recovery = calc_recovery(&defence, &skill);
+    assert_eq!(recovery["Life Recovery/s"], 50);
+    assert_eq!(recovery["Mana Recovery/s"], 0);
+    assert_eq!(recovery["ES Recovery/s"], 105);
+}
+
+#[test]
+fn test_exert_more_damage() {
+    let warcry = |name: &str, cycle| WarcryUse { warcry: &WARCRY_STATS[name], cycle, uptime: 100 };
+    assert_eq!(calc_exert_more_damage(&[], 1000), 0);
+    // 3 exerted attacks every 3s exert every attack
+    assert_eq!(calc_exert_more_damage(&[warcry("Intimidating Cry", 3000)], 1000), 100);
+    assert_eq!(calc_exert_more_damage(&[warcry("Intimidating Cry", 6000)], 1000), 50);
+    assert_eq!(calc_exert_more_damage(&[warcry("Intimidating Cry", 0)], 1000), 100);
+    // Exerts from different warcries multiply
+    assert_eq!(calc_exert_more_damage(&[warcry("Intimidating Cry", 3000), warcry("Seismic Cry", 3000)], 1000), 150);
+}
+
+#[test]
+fn test_warcry_debuffs() {
+    // Intimidate at half uptime, taken only from attacks
+    let intimidate = |tags: BitFlags<GemTag>| {
+        let mods: Vec<Mod> = warcry_mods(&WARCRY_STATS["Intimidating Cry"].debuffs, 50).filter(|m| tags.contains(m.tags)).collect();
+        build::stat::calc_stats(&mods).stat(StatId::DamageTaken).val_custom(100)
+    };
+    assert_eq!(intimidate(GemTag::Attack | GemTag::Melee), 105);
+    assert_eq!(intimidate(GemTag::Spell.into()), 100);
+    assert!(calc_warcry_uses(&Build::new_player()).is_empty());
+}
+
+#[test]
+fn test_gained_as_extra() {
+    // Gained from the cold damage before it is converted, scaled by cold and fire modifiers
+    let stats = test_stats(&[(StatId::ColdGainedAsFire, Type::Base, 20), (StatId::ColdToFireConversion, Type::Base, 50)]);
+    let portions = apply_conversion(&stats, &[0, 0, 1000, 0, 0]);
+    assert_eq!(portions[DamageType::Cold.as_index()].iter().map(|p| p.amount).sum::<i64>(), 500);
+    assert_eq!(portions[DamageType::Fire.as_index()].iter().map(|p| p.amount).sum::<i64>(), 700);
+    assert!(portions[DamageType::Fire.as_index()].iter().all(|p| p.source_types == make_bitflags!(DamageType::{Cold | Fire})));
+}
+
+#[test]
+fn test_skill_leeches() {
+    let mut player = Build::new_player();
+    let mut mods = crate::modifier::parse_mod("0.3% of physical attack damage leeched as life", Source::Innate).unwrap();
+    assert!(skill_leeches(&player, &mods, GemTag::Attack.into()));
+    assert!(!skill_leeches(&player, &mods, GemTag::Spell.into()));
+    assert!(!skill_leeches(&player, &[], GemTag::Attack.into()));
+
+    // The config forces the mods applying while leeching
+    mods.extend(crate::modifier::parse_mod("10% increased armour while leeching", Source::Innate).unwrap());
+    assert_eq!(player.calc_stats(&mods, BitFlags::EMPTY, BitFlags::EMPTY).stat(StatId::Armour).inc, 0);
+    player.set_property_bool(property::Bool::Leeching, true);
+    assert_eq!(player.calc_stats(&mods, BitFlags::EMPTY, BitFlags::EMPTY).stat(StatId::Armour).inc, 10);
+}
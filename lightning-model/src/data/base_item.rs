@@ -1,4 +1,4 @@
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
 use enumflags2::{BitFlags, bitflags, make_bitflags as flags};
 
@@ -100,6 +100,19 @@ pub struct Properties {
     pub energy_shield: Option<PropertyMinMax>,
     pub critical_strike_chance: Option<i64>,
     pub block: Option<i64>,
+    pub charges_max: Option<i64>,
+    pub charges_per_use: Option<i64>,
+    /// Flask duration, in tenths of a second
+    pub duration: Option<i64>,
+    pub life_per_use: Option<i64>,
+    pub mana_per_use: Option<i64>,
+}
+
+/// Buff granted by using a utility flask, stats are keyed by game stat id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrantedBuff {
+    pub id: String,
+    pub stats: FxHashMap<String, i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -118,6 +131,36 @@ pub struct BaseItem {
     pub item_class: ItemClass,
     pub properties: Properties,
     pub requirements: Option<Requirements>,
+    pub grants_buff: Option<GrantedBuff>,
+}
+
+impl BaseItem {
+    /// Base item without properties, fitting no slot
+    pub fn missing() -> BaseItem {
+        BaseItem {
+            name: "Unknown Item".to_string(),
+            tags: FxHashSet::default(),
+            implicits: vec![],
+            item_class: ItemClass::Unarmed,
+            properties: Properties {
+                armour: None,
+                physical_damage_max: None,
+                physical_damage_min: None,
+                attack_time: None,
+                evasion: None,
+                energy_shield: None,
+                critical_strike_chance: None,
+                block: None,
+                charges_max: None,
+                charges_per_use: None,
+                duration: None,
+                life_per_use: None,
+                mana_per_use: None,
+            },
+            requirements: None,
+            grants_buff: None,
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
@@ -30,6 +30,27 @@ pub struct Costs {
     pub life: Option<i32>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Reservations {
+    pub mana_flat: Option<i64>,
+    pub mana_percent: Option<f32>,
+    pub life_flat: Option<i64>,
+    pub life_percent: Option<f32>,
+}
+
+/// Monster summoned by a minion gem, scaling the default ally stats of its level
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MinionData {
+    /// Percentage of the default ally life
+    pub life_multiplier: i64,
+    /// Percentage of the default monster damage
+    pub damage_multiplier: i64,
+    /// Time in ms of the minion's attack
+    pub attack_time: i64,
+    /// Hundredths of a percent
+    pub crit_chance: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct StatRequirements {
     #[serde(default)]
@@ -56,6 +77,8 @@ pub struct Level {
     pub damage_multiplier: Option<i64>,
     pub cost_multiplier: Option<i64>,
     #[serde(default)]
+    pub reservations: Option<Reservations>,
+    #[serde(default)]
     pub stat_text: Option<FxHashMap<String, String>>,
 }
 
@@ -326,10 +349,45 @@ pub struct GemData {
     pub tooltip_order: Vec<String>,
     #[serde(default)]
     pub support_gem: Option<SupportGemData>,
+    /// Monster stats of the minion gems, from the exporter
+    #[serde(default)]
+    pub minion: Option<MinionData>,
     pub color: String,
 }
 
 impl GemData {
+    /// Gem data without any skill or stat
+    pub fn missing() -> GemData {
+        GemData {
+            active_skill: None,
+            base_item: BaseItem {
+                display_name: "Unknown Gem".to_string(),
+                id: String::new(),
+                max_level: None,
+                release_state: String::new(),
+            },
+            cast_time: None,
+            is_support: false,
+            per_level: FxHashMap::default(),
+            r#static: Static {
+                crit_chance: None,
+                cooldown: None,
+                damage_effectiveness: None,
+                damage_multiplier: None,
+                attack_speed_multiplier: None,
+                stats: None,
+                quality_stats: vec![],
+                stat_text: None,
+            },
+            tags: FxHashSet::default(),
+            weapon_restrictions: FxHashSet::default(),
+            tooltip_order: vec![],
+            support_gem: None,
+            minion: None,
+            color: String::new(),
+        }
+    }
+
     pub fn display_name(&'static self) -> &'static str {
         if let Some(active_skill) = self.active_skill.as_ref() {
             &active_skill.display_name
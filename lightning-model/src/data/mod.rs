@@ -89,4 +89,7 @@ lazy_static! {
         bincode::deserialize(include_bytes!("../../data/default_monster_stats.bc")).expect("Failed to deserialize default monster stats");
     pub static ref TATTOOS: FxHashMap<String, TattooData> =
         bincode::deserialize(include_bytes!("../../data/tattoos.bc")).expect("Failed to deserialize tattoos");
+    /// Stand-ins for gems and base items of a build missing from GEMS and ITEMS
+    pub static ref MISSING_GEM: GemData = GemData::missing();
+    pub static ref MISSING_BASE_ITEM: BaseItem = BaseItem::missing();
 }
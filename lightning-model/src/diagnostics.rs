@@ -0,0 +1,105 @@
+use std::fmt;
+
+use crate::build::stat::StatId;
+use crate::modifier::Source;
+
+/// Something the calculations had to skip, numbers depending on it are incomplete
+#[derive(Debug, Clone, PartialEq)]
+pub enum Diagnostic {
+    /// Stats depending on each other, from the first stat of the loop back to itself.
+    /// The stat closing the loop is evaluated as 0 at that point.
+    CircularDependency(Vec<StatId>),
+    /// Mod text that couldn't be parsed and is ignored
+    UnparsedMod { text: String, source: Source },
+    /// Reference to game data that doesn't exist, the entry is left out of the calculations
+    MissingData { kind: DataKind, name: String },
+    /// Known entry lacking the data a calculation needs, that calculation is left out for it
+    IncompleteData { kind: DataKind, name: String },
+    /// Chosen effect the calculations don't model, like a pantheon power
+    UnmodelledEffect { name: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+pub enum DataKind {
+    #[strum(serialize = "gem")]
+    Gem,
+    #[strum(serialize = "base item")]
+    BaseItem,
+    #[strum(serialize = "mastery effect")]
+    MasteryEffect,
+    #[strum(serialize = "ascendancy")]
+    Ascendancy,
+    #[strum(serialize = "minion")]
+    Minion,
+    #[strum(serialize = "reservation")]
+    Reservation,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Diagnostic::CircularDependency(path) => {
+                let path: Vec<String> = path.iter().map(|stat_id| stat_id.to_string()).collect();
+                write!(f, "Circular dependency: {}", path.join(" -> "))
+            },
+            Diagnostic::UnparsedMod { text, source } => {
+                write!(f, "Unparsed mod \"{text}\"")?;
+                match source {
+                    Source::Innate => Ok(()),
+                    Source::Node(id) => write!(f, " on node {id}"),
+                    Source::Mastery((id, effect)) => write!(f, " on mastery {id} / {effect}"),
+                    Source::Item(slot) => write!(f, " on {slot}"),
+                    Source::Gem(name) | Source::Custom(name) => write!(f, " from {name}"),
+                }
+            },
+            Diagnostic::MissingData { kind, name } => write!(f, "Unknown {kind}: {name}"),
+            Diagnostic::IncompleteData { kind, name } => write!(f, "Missing {kind} data for {name}"),
+            Diagnostic::UnmodelledEffect { name } => write!(f, "Effect not modelled: {name}"),
+        }
+    }
+}
+
+/// Diagnostics gathered while loading or evaluating a build, without duplicates
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        if !self.diagnostics.contains(&diagnostic) {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    pub fn extend(&mut self, diagnostics: impl IntoIterator<Item = Diagnostic>) {
+        for diagnostic in diagnostics {
+            self.push(diagnostic);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.diagnostics.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.diagnostics.clear();
+    }
+}
+
+impl IntoIterator for Diagnostics {
+    type Item = Diagnostic;
+    type IntoIter = std::vec::IntoIter<Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.diagnostics.into_iter()
+    }
+}
@@ -4,8 +4,8 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::build::stat::StatId;
-use crate::data::gem::{GemData, GemTag};
-use crate::data::{DamageType, GEMS};
+use crate::data::gem::{ActiveSkillType, GemData, GemTag, Reservations};
+use crate::data::{DamageType, GEMS, MISSING_GEM};
 use crate::gemstats;
 use crate::modifier::{Mod, ModFlag, Source, Type};
 use crate::{item, util};
@@ -24,6 +24,12 @@ pub struct Gem {
     pub level: u32,
     pub qual: i32,
     pub alt_qual: i32,
+    /// Counts towards the combined DPS of the build
+    #[serde(default)]
+    pub full_dps: bool,
+    /// Percentage of the time the skill is in use, for the combined DPS
+    #[serde(default = "default_uptime")]
+    pub uptime: i64,
     #[serde(skip)]
     #[derivative(Clone(clone_with = "clone_arc_swap"))]
     mod_cache: ArcSwap<Vec<Mod>>,
@@ -35,6 +41,10 @@ pub struct Gem {
     is_modcache_fresh: AtomicBool,
 }
 
+fn default_uptime() -> i64 {
+    100
+}
+
 fn clone_arc_swap<T>(cache: &ArcSwap<T>) -> ArcSwap<T> {
     ArcSwap::new(cache.load_full())
 }
@@ -57,14 +67,21 @@ impl Gem {
             level,
             qual,
             alt_qual,
+            full_dps: false,
+            uptime: default_uptime(),
             mod_cache: Default::default(),
             mod_cache_auras: Default::default(),
             is_modcache_fresh: Default::default(),
         }
     }
 
+    /// Placeholder data when the gem is missing from the game data, see `has_data`
     pub fn data(&self) -> &'static GemData {
-        &GEMS[&self.id]
+        GEMS.get(&self.id).unwrap_or(&MISSING_GEM)
+    }
+
+    pub fn has_data(&self) -> bool {
+        GEMS.contains_key(&self.id)
     }
 
     pub fn can_support(&self, active_gem: &Gem) -> bool {
@@ -206,6 +223,25 @@ impl Gem {
         level_data.cost_multiplier
     }
 
+    pub fn reservations_level(&self) -> Option<&'static Reservations> {
+        self.data().per_level.get(&self.level)?.reservations.as_ref()
+    }
+
+    /// Reservations of the gem's level, or of the closest level having reservation data
+    pub fn reservations(&self) -> Option<&'static Reservations> {
+        self.reservations_level().or_else(|| {
+            self.data().per_level.iter()
+                .filter_map(|(level, level_data)| Some((*level, level_data.reservations.as_ref()?)))
+                .min_by_key(|(level, _)| (level.abs_diff(self.level), *level))
+                .map(|(_, reservations)| reservations)
+        })
+    }
+
+    /// Whether the skill reserves anything, even if the data of its level doesn't say how much
+    pub fn has_reservation(&self) -> bool {
+        self.data().active_skill.as_ref().is_some_and(|s| s.types.contains(&ActiveSkillType::HasReservation)) || self.reservations().is_some()
+    }
+
     fn stat_value_level(&self, id: &str) -> Option<i64> {
         let idx = self.data().r#static.stat_idx(id)?;
         let level_data = self.data().per_level.get(&self.level)?;
@@ -240,6 +276,10 @@ impl Gem {
         self.data().r#static.crit_chance
     }
 
+    pub fn cooldown(&self) -> Option<i64> {
+        self.data().r#static.cooldown.map(i64::from)
+    }
+
     pub fn added_effectiveness(&self) -> Option<i64> {
         if let Some(level_data) = self.data().per_level.get(&self.level) {
             if level_data.damage_effectiveness.is_some() {
@@ -1,6 +1,7 @@
 use crate::build::stat::StatId;
 use crate::data::gem::GemTag;
-use crate::modifier::{Mod, Type, ModFlag};
+use crate::modifier::{Mod, Type, ModFlag, Mutation};
+use crate::stackvec;
 use rustc_hash::FxHashMap;
 use enumflags2::{make_bitflags as flags, BitFlags};
 use lazy_static::lazy_static;
@@ -33,6 +34,51 @@ lazy_static! {
         ("spell_maximum_base_chaos_damage", vec![
             Mod { stat: StatId::BaseMaxChaosDamage, tags: flags!(GemTag::Spell), ..Default::default() },
         ]),
+        ("fire_damage_to_deal_per_minute", vec![
+            Mod { stat: StatId::BaseFireDamageOverTime, ..Default::default() },
+        ]),
+        ("cold_damage_to_deal_per_minute", vec![
+            Mod { stat: StatId::BaseColdDamageOverTime, ..Default::default() },
+        ]),
+        ("chaos_damage_to_deal_per_minute", vec![
+            Mod { stat: StatId::BaseChaosDamageOverTime, ..Default::default() },
+        ]),
+        ("physical_damage_to_deal_per_minute", vec![
+            Mod { stat: StatId::BasePhysicalDamageOverTime, ..Default::default() },
+        ]),
+        ("number_of_zombies_allowed", vec![
+            Mod { stat: StatId::MaximumMinions, ..Default::default() },
+        ]),
+        ("number_of_skeletons_allowed", vec![
+            Mod { stat: StatId::MaximumMinions, ..Default::default() },
+        ]),
+        ("number_of_spectres_allowed", vec![
+            Mod { stat: StatId::MaximumMinions, ..Default::default() },
+        ]),
+        ("number_of_totems_allowed", vec![
+            Mod { stat: StatId::MaximumTotems, ..Default::default() },
+        ]),
+        ("number_of_traps_allowed", vec![
+            Mod { stat: StatId::MaximumTraps, ..Default::default() },
+        ]),
+        ("number_of_remote_mines_allowed", vec![
+            Mod { stat: StatId::MaximumMines, ..Default::default() },
+        ]),
+        ("number_of_additional_traps_to_throw", vec![
+            Mod { stat: StatId::AdditionalTraps, ..Default::default() },
+        ]),
+        ("number_of_additional_mines_to_place", vec![
+            Mod { stat: StatId::AdditionalMines, ..Default::default() },
+        ]),
+        ("summon_totem_cast_speed", vec![
+            Mod { stat: StatId::TotemPlacementSpeed, ..Default::default() },
+        ]),
+        ("trap_throwing_speed", vec![
+            Mod { stat: StatId::TrapThrowingSpeed, ..Default::default() },
+        ]),
+        ("mine_laying_speed", vec![
+            Mod { stat: StatId::MineThrowingSpeed, ..Default::default() },
+        ]),
         ("minimum_added_fire_damage", vec![
             Mod { stat: StatId::AddedMinFireDamage, ..Default::default() },
         ]),
@@ -236,6 +282,56 @@ lazy_static! {
                 Mod { stat: StatId::Armour, flags: flags!(ModFlag::Aura), ..Default::default() },
             ]),
         ].into_iter().collect()),
+        ("Righteous Fire", [
+            ("base_righteous_fire_%_of_max_life_to_deal_to_nearby_per_minute", vec![
+                Mod { stat: StatId::BaseFireDamageOverTime, mutations: stackvec![Mutation::MultiplierStat((100, StatId::MaximumLife))], ..Default::default() },
+            ]),
+            ("base_righteous_fire_%_of_max_energy_shield_to_deal_to_nearby_per_minute", vec![
+                Mod { stat: StatId::BaseFireDamageOverTime, mutations: stackvec![Mutation::MultiplierStat((100, StatId::MaximumEnergyShield))], ..Default::default() },
+            ]),
+        ].into_iter().collect()),
+        ("Elemental Weakness", [
+            ("base_resist_all_elements", vec![
+                Mod { stat: StatId::FireResistance, flags: flags!(ModFlag::Curse), ..Default::default() },
+                Mod { stat: StatId::ColdResistance, flags: flags!(ModFlag::Curse), ..Default::default() },
+                Mod { stat: StatId::LightningResistance, flags: flags!(ModFlag::Curse), ..Default::default() },
+            ]),
+        ].into_iter().collect()),
+        ("Flammability", [
+            ("base_fire_damage_resistance", vec![
+                Mod { stat: StatId::FireResistance, flags: flags!(ModFlag::Curse), ..Default::default() },
+            ]),
+        ].into_iter().collect()),
+        ("Frostbite", [
+            ("base_cold_damage_resistance", vec![
+                Mod { stat: StatId::ColdResistance, flags: flags!(ModFlag::Curse), ..Default::default() },
+            ]),
+        ].into_iter().collect()),
+        ("Conductivity", [
+            ("base_lightning_damage_resistance", vec![
+                Mod { stat: StatId::LightningResistance, flags: flags!(ModFlag::Curse), ..Default::default() },
+            ]),
+        ].into_iter().collect()),
+        ("Despair", [
+            ("base_chaos_damage_resistance", vec![
+                Mod { stat: StatId::ChaosResistance, flags: flags!(ModFlag::Curse), ..Default::default() },
+            ]),
+        ].into_iter().collect()),
+        ("Vulnerability", [
+            ("physical_damage_taken", vec![
+                Mod { stat: StatId::PhysicalDamageTaken, flags: flags!(ModFlag::Curse), ..Default::default() },
+            ]),
+        ].into_iter().collect()),
+        ("Punishment", [
+            ("newpunishment_melee_physical_damage", vec![
+                Mod { stat: StatId::PhysicalDamageTaken, tags: flags!(GemTag::Melee), flags: flags!(ModFlag::Curse), ..Default::default() },
+            ]),
+        ].into_iter().collect()),
+        ("Sniper's Mark", [
+            ("projectile_damage_taken", vec![
+                Mod { stat: StatId::DamageTaken, tags: flags!(GemTag::Projectile), flags: flags!(ModFlag::Curse), ..Default::default() },
+            ]),
+        ].into_iter().collect()),
        /* ("Vitality", [
             // Should be per minute
             ("base_life_regeneration_rate_per_minute", vec![
@@ -294,3 +390,11 @@ pub fn match_gemstat(gem_basename: &str, mut stat: &str) -> Option<Vec<Mod>> {
 
     Some(mods)
 }
+
+#[test]
+fn test_curse_gemstats() {
+    let punishment = match_gemstat("Punishment", "newpunishment_melee_physical_damage_+%_final").unwrap();
+    assert!(punishment.iter().all(|m| m.stat == StatId::PhysicalDamageTaken && m.typ == Type::More && m.flags.contains(ModFlag::Curse)));
+    let mark = match_gemstat("Sniper's Mark", "projectile_damage_taken_+%").unwrap();
+    assert!(mark.iter().all(|m| m.stat == StatId::DamageTaken && m.typ == Type::Inc && m.flags.contains(ModFlag::Curse)));
+}
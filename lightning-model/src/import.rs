@@ -6,6 +6,7 @@ use crate::build::{self, Build, GemLink, Slot};
 use crate::data::base_item::{self, Rarity};
 use crate::data::tree::{Ascendancy, Class, ExpansionJewel};
 use crate::data::{GEMS, ITEMS, TREE};
+use crate::diagnostics::{DataKind, Diagnostic, Diagnostics};
 use crate::gem;
 use crate::item;
 use serde::Deserialize;
@@ -122,7 +123,7 @@ impl Item {
     }
 }
 
-fn extract_socketed(gems: &Vec<Item>) -> (GemLink, Vec<item::Item>) {
+fn extract_socketed(gems: &Vec<Item>, diagnostics: &mut Diagnostics) -> (GemLink, Vec<item::Item>) {
     let mut gemlink = GemLink {
         gems: vec![],
         slot: build::Slot::Helm,
@@ -151,7 +152,8 @@ fn extract_socketed(gems: &Vec<Item>) -> (GemLink, Vec<item::Item>) {
         } else if let Some(jewel) = conv_item(gem) {
             jewels.push(jewel);
         } else {
-            eprintln!("Failed to import item {}", gem.baseType);
+            let kind = if gem.properties.iter().any(|p| p.name == "Level") { DataKind::Gem } else { DataKind::BaseItem };
+            diagnostics.push(Diagnostic::MissingData { kind, name: gem.baseType.clone() });
         }
     }
 
@@ -197,7 +199,8 @@ impl std::fmt::Display for ParseError {
 }
 impl std::error::Error for ParseError {}
 
-pub fn character(account: &str, character: &str) -> Result<Build, Box<dyn Error>> {
+/// Imports a character, along with the gems and items that couldn't be imported
+pub fn character(account: &str, character: &str) -> Result<(Build, Diagnostics), Box<dyn Error>> {
     let client = reqwest::blocking::ClientBuilder::new().user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:126.0) Gecko/20100101 Firefox/126.0").build()?;
 
     // Passive Tree
@@ -211,6 +214,7 @@ pub fn character(account: &str, character: &str) -> Result<Build, Box<dyn Error>
     let items_import = client.get(url).send()?.json::<ItemsSkillsChar>()?;
 
     let mut build = Build::new_player();
+    let mut diagnostics = Diagnostics::default();
     let mut abyssal_jewel_idx = 0;
     build.name = character.to_string();
     build.set_property_int(crate::build::property::Int::Level, items_import.character.level);
@@ -228,7 +232,7 @@ pub fn character(account: &str, character: &str) -> Result<Build, Box<dyn Error>
         if let Ok(mastery) = u32::from_str(mastery) {
             build.tree.masteries.insert(mastery as u32, *selected as u32);
         } else {
-            eprintln!("Couldn't parse mastery effect id: {mastery}");
+            diagnostics.push(Diagnostic::MissingData { kind: DataKind::MasteryEffect, name: mastery.clone() });
         }
     }
 
@@ -242,17 +246,17 @@ pub fn character(account: &str, character: &str) -> Result<Build, Box<dyn Error>
             if let Ok(bloodline) = Ascendancy::from_str(bloodline_str) {
                 build.tree.set_bloodline(Some(bloodline));
             } else {
-                eprintln!("Failed to match alternate ascendancy {}", bloodline_str);
+                diagnostics.push(Diagnostic::MissingData { kind: DataKind::Ascendancy, name: bloodline_str.clone() });
             }
         } else {
-            eprintln!("Failed to find alternate ascendancy index {}", alternate_ascendancy - 1);
+            diagnostics.push(Diagnostic::MissingData { kind: DataKind::Ascendancy, name: format!("index {}", alternate_ascendancy - 1) });
         }
     }
 
     let mut to_equip = vec![];
     for item in tree_import.items.iter().chain(items_import.items.iter()) {
         if let Some(socketed_items) = &item.socketedItems {
-            let (gemlink, jewels) = extract_socketed(socketed_items);
+            let (gemlink, jewels) = extract_socketed(socketed_items, &mut diagnostics);
             build.gem_links.push(gemlink);
             for jewel in jewels {
                 build.inventory.push(Arc::new(jewel));
@@ -266,6 +270,8 @@ pub fn character(account: &str, character: &str) -> Result<Build, Box<dyn Error>
                 if let Ok(slot) = Slot::try_from((inventory_id.as_str(), item.x.unwrap_or(0))) {
                     to_equip.push((slot, build.inventory.len() - 1));
                 }
+            } else {
+                diagnostics.push(Diagnostic::MissingData { kind: DataKind::BaseItem, name: item.baseType.clone() });
             }
         }
     }
@@ -337,5 +343,5 @@ pub fn character(account: &str, character: &str) -> Result<Build, Box<dyn Error>
         build::CampaignChoice::Beach
     };
 
-    Ok(build)
+    Ok((build, diagnostics))
 }
@@ -1,9 +1,9 @@
-use crate::build::stat::{calc_stat, Stat, StatId};
+use crate::build::stat::{calc_stat, Stat, StatId, Stats};
 use crate::build::Slot;
 use crate::data::base_item::{BaseItem, Rarity};
 use crate::data::tree::Node;
-use crate::data::{DAMAGE_GROUPS, DamageType, ITEMS, TREE};
-use crate::modifier::{self, parse_mod, Mod, Source, Type};
+use crate::data::{DAMAGE_GROUPS, DamageType, ITEMS, MISSING_BASE_ITEM, TREE};
+use crate::modifier::{self, parse_flask_mod, parse_mod, Mod, Source, Type};
 use arc_swap::ArcSwap;
 use derivative::Derivative;
 use regex::Regex;
@@ -92,6 +92,38 @@ const LOCAL_MODS_ARMOUR: &[LocalModMatch] = &[
     LocalModMatch { stat: StatId::ChanceToBlockAttackDamage, typ: modifier::Type::Inc },
 ];
 
+const LOCAL_MODS_FLASK: &[LocalModMatch] = &[
+    LocalModMatch { stat: StatId::Duration, typ: modifier::Type::Inc },
+    LocalModMatch { stat: StatId::FlaskChargesUsed, typ: modifier::Type::Inc },
+    LocalModMatch { stat: StatId::FlaskChargesGained, typ: modifier::Type::Inc },
+    LocalModMatch { stat: StatId::FlaskMaximumCharges, typ: modifier::Type::Base },
+    LocalModMatch { stat: StatId::FlaskEffect, typ: modifier::Type::Inc },
+    LocalModMatch { stat: StatId::FlaskRecoveryAmount, typ: modifier::Type::Inc },
+    LocalModMatch { stat: StatId::FlaskRecoveryRate, typ: modifier::Type::Inc },
+];
+
+/// Utility flask buff stats, as found in the base item data
+const FLASK_BUFF_STATS: &[(&str, &[StatId], Type)] = &[
+    ("critical_strike_chance_+%", &[StatId::CriticalStrikeChance], Type::Inc),
+    ("base_fire_damage_resistance_%", &[StatId::FireResistance], Type::Base),
+    ("base_cold_damage_resistance_%", &[StatId::ColdResistance], Type::Base),
+    ("base_lightning_damage_resistance_%", &[StatId::LightningResistance], Type::Base),
+    ("base_chaos_damage_resistance_%", &[StatId::ChaosResistance], Type::Base),
+    ("base_maximum_fire_damage_resistance_%", &[StatId::MaximumFireResistance], Type::Base),
+    ("base_maximum_cold_damage_resistance_%", &[StatId::MaximumColdResistance], Type::Base),
+    ("base_maximum_lightning_damage_resistance_%", &[StatId::MaximumLightningResistance], Type::Base),
+    ("base_resist_all_elements_%", &[StatId::FireResistance, StatId::ColdResistance, StatId::LightningResistance], Type::Base),
+    ("base_physical_damage_reduction_rating", &[StatId::Armour], Type::Base),
+    ("basalt_flask_armour_+%_final", &[StatId::Armour], Type::More),
+    ("base_evasion_rating", &[StatId::EvasionRating], Type::Base),
+    ("stibnite_flask_evasion_rating_+%_final", &[StatId::EvasionRating], Type::More),
+    ("base_movement_velocity_+%", &[StatId::MovementSpeed], Type::Inc),
+    ("base_spell_suppression_chance_%", &[StatId::ChanceToSuppressSpellDamage], Type::Base),
+    ("damage_+%", &[StatId::Damage], Type::Inc),
+    ("stun_threshold_+%", &[StatId::StunThreshold], Type::Inc),
+    ("base_ward", &[StatId::Ward], Type::Base),
+];
+
 fn match_local(m: &Mod, match_table: &[LocalModMatch]) -> bool {
     if !m.conditions.is_empty() || !m.mutations.is_empty() {
         return false;
@@ -112,6 +144,25 @@ pub struct DefenceCalc {
     pub block_chance: Stat,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct FlaskCalc {
+    pub charges_max: i64,
+    pub charges_per_use: i64,
+    /// Percentage of the base charges gained
+    pub charges_gained: i64,
+    /// Milliseconds
+    pub duration: i64,
+    pub life: i64,
+    pub mana: i64,
+}
+
+/// Cluster jewel mods handled by `get_cluster` instead of the mod parser
+const CLUSTER_MOD_PREFIXES: &[&str] = &[
+    "Added Small Passive Skills grant: ",
+    "Added Small Passive Skills also grant: ",
+    "1 Added Passive Skill is ",
+];
+
 #[derive(Debug)]
 pub struct ClusterData<'a> {
     pub small_passives_amount: u32,
@@ -122,8 +173,13 @@ pub struct ClusterData<'a> {
 }
 
 impl Item {
+    /// Placeholder data when the base item is missing from the game data, see `has_data`
     pub fn data(&self) -> &'static BaseItem {
-        &ITEMS[&self.base_item]
+        ITEMS.get(&self.base_item).unwrap_or(&MISSING_BASE_ITEM)
+    }
+
+    pub fn has_data(&self) -> bool {
+        ITEMS.contains_key(&self.base_item)
     }
 
     fn get_small_passive_grant(&self) -> Option<u32> {
@@ -332,10 +388,86 @@ impl Item {
         None
     }
 
+    /// Flask properties after its local mods and the player's global flask mods in `stats`, None for non-flask items
+    pub fn calc_flask(&self, stats: &Stats) -> Option<FlaskCalc> {
+        let props = &self.data().properties;
+        let duration = props.duration?;
+        let mods = self.calc_local_mods();
+        let recovers = props.life_per_use.is_some() || props.mana_per_use.is_some();
+        let local_and_global = |stat_id: StatId, global_stat_id: StatId| {
+            let mut stat = calc_stat(stat_id, &mods);
+            stat.assimilate(stats.stat(global_stat_id));
+            stat
+        };
+
+        let mut stat_duration = local_and_global(StatId::Duration, StatId::FlaskEffectDuration);
+        let mut stat_recovery = local_and_global(StatId::FlaskRecoveryAmount, StatId::FlaskRecoveryAmount);
+        // Quality adds to the amount recovered on life and mana flasks, to the duration on the others
+        if recovers {
+            stat_recovery.adjust(Type::Inc, self.quality);
+        } else {
+            stat_duration.adjust(Type::Inc, self.quality);
+        }
+        let mut duration = stat_duration.val_custom(duration * 100);
+        if recovers {
+            duration = local_and_global(StatId::FlaskRecoveryRate, StatId::FlaskRecoveryRate).val_custom_inv(duration);
+        }
+
+        let mut charges_max = calc_stat(StatId::FlaskMaximumCharges, &mods);
+        charges_max.adjust(Type::Base, props.charges_max.unwrap_or(0));
+
+        Some(FlaskCalc {
+            charges_max: charges_max.val(),
+            charges_per_use: local_and_global(StatId::FlaskChargesUsed, StatId::FlaskChargesUsed).val_custom(props.charges_per_use.unwrap_or(0)),
+            charges_gained: local_and_global(StatId::FlaskChargesGained, StatId::FlaskChargesGained).val_custom(100),
+            duration,
+            life: stat_recovery.val_custom(props.life_per_use.unwrap_or(0)),
+            mana: stat_recovery.val_custom(props.mana_per_use.unwrap_or(0)),
+        })
+    }
+
+    /// Mods applied while the flask is active: its buff and "during effect" mods,
+    /// scaled by the flask's own increased effect
+    pub fn calc_flask_mods(&self) -> Vec<Mod> {
+        let mut ret: Vec<Mod> = self.calc_nonlocal_mods().to_vec();
+        if let Some(buff) = &self.data().grants_buff {
+            for (stat, stat_ids, typ) in FLASK_BUFF_STATS {
+                if let Some(amount) = buff.stats.get(*stat) {
+                    ret.extend(stat_ids.iter().map(|stat_id| Mod { stat: *stat_id, typ: *typ, amount: *amount, ..Default::default() }));
+                }
+            }
+        }
+
+        let effect = calc_stat(StatId::FlaskEffect, &self.calc_local_mods());
+        for m in ret.iter_mut() {
+            m.amount = effect.val_custom(m.amount);
+        }
+        ret
+    }
+
     pub fn allocates_nodes(&self) -> bool {
         self.calc_nonlocal_mods().iter().find(|m| m.allocates.is_some()).is_some()
     }
 
+    /// Mods the parser doesn't support, except the cluster jewel ones read by `get_cluster`
+    pub fn unparsed_mods(&self) -> impl Iterator<Item = &String> {
+        let is_cluster = self.data().name.ends_with("Cluster Jewel");
+        self.mods_impl.iter().chain(&self.mods_expl).chain(&self.mods_enchant).filter(move |m| {
+            if is_cluster && CLUSTER_MOD_PREFIXES.iter().any(|prefix| m.starts_with(prefix)) {
+                return false;
+            }
+            self.parse_mod(m).is_none()
+        })
+    }
+
+    fn parse_mod(&self, m: &str) -> Option<Vec<Mod>> {
+        if self.data().tags.contains("flask") {
+            parse_flask_mod(m, Source::Innate)
+        } else {
+            parse_mod(m, Source::Innate)
+        }
+    }
+
     fn calc_mods(&self, local: bool) -> Vec<Mod> {
         let mut mods = Vec::with_capacity(12);
         let mut match_table: &[LocalModMatch] = &[];
@@ -345,10 +477,12 @@ impl Item {
             match_table = &LOCAL_MODS_WEAPON;
         } else if tags.contains("armour") {
             match_table = &LOCAL_MODS_ARMOUR;
+        } else if tags.contains("flask") {
+            match_table = LOCAL_MODS_FLASK;
         }
 
         for m in self.mods_impl.iter().chain(&self.mods_expl).chain(&self.mods_enchant) {
-            if let Some(modifiers) = parse_mod(m, Source::Innate) {
+            if let Some(modifiers) = self.parse_mod(m) {
                 mods.extend(modifiers.into_iter().filter(|m| (local && match_local(m, match_table)) || (!local && !match_local(m, match_table))));
             }
         }
@@ -526,3 +660,23 @@ impl Item {
         output
     }
 }
+
+#[test]
+fn test_calc_flask() {
+    let global = crate::build::stat::calc_stats(&[Mod { stat: StatId::FlaskEffectDuration, typ: Type::Inc, amount: 10, ..Default::default() }]);
+    let utility = Item { base_item: "Quicksilver Flask".to_string(), mods_expl: vec!["+10 to maximum charges".to_string()], quality: 20, ..Default::default() };
+    let flask = utility.calc_flask(&global).unwrap();
+    assert_eq!(flask.charges_max, 70);
+    assert_eq!(flask.charges_per_use, 30);
+    // Quality and the global mod both add to the duration of a utility flask
+    assert_eq!(flask.duration, 7800);
+
+    let life = Item { base_item: "Divine Life Flask".to_string(), quality: 20, ..Default::default() };
+    let flask = life.calc_flask(&global).unwrap();
+    // On a life flask quality goes to the amount recovered instead
+    assert_eq!(flask.life, 2880);
+    assert_eq!(flask.duration, 3850);
+
+    let ring = Item { base_item: "Iron Ring".to_string(), ..Default::default() };
+    assert!(ring.calc_flask(&global).is_none());
+}
@@ -11,6 +11,7 @@ pub mod macros;
 pub mod build;
 pub mod calc;
 pub mod data;
+pub mod diagnostics;
 pub mod gem;
 mod gemstats;
 pub mod item;
@@ -101,7 +101,18 @@ const ENDINGS: &[(&str, BitFlags<GemTag>, BitFlags<ItemClass>, BitFlags<ModFlag>
     ("while fortified", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::GreaterEqualProperty((1, property::Int::Fortification))]),
     ("if you've dealt a critical strike recently", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::DealtCritRecently))]),
     ("if you've blocked recently", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::BlockedRecently))]),
-    ("while leeching", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::Leeching))]),
+    ("while leeching", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::Leeching]),
+    ("while you have onslaught", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::Onslaught))]),
+    ("while you have arcane surge", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::ArcaneSurge))]),
+    ("while you are elusive", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::Elusive))]),
+    ("while elusive", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::Elusive))]),
+    ("while you have tailwind", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::Tailwind))]),
+    ("while you have unholy might", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::UnholyMight))]),
+    ("while you have adrenaline", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::Adrenaline))]),
+    ("during any flask effect", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::UsingFlask]),
+    ("during flask effect", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::UsingFlask]),
+    // Mods on the flask itself
+    ("during effect", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::UsingFlask]),
     ("when on full life", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::OnFullLife))]),
     ("while on full energy shield", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::OnFullEnergyShield))]),
     ("while on full life", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[Condition::PropertyBool((true, property::Bool::OnFullLife))]),
@@ -129,6 +140,8 @@ const ENDINGS: &[(&str, BitFlags<GemTag>, BitFlags<ItemClass>, BitFlags<ModFlag>
     ("for bleeding", BitFlags::EMPTY, BitFlags::EMPTY, flags!(ModFlag::Bleed), &[]),
     ("with poison", BitFlags::EMPTY, BitFlags::EMPTY, flags!(ModFlag::Poison), &[]),
     ("for poison", BitFlags::EMPTY, BitFlags::EMPTY, flags!(ModFlag::Poison), &[]),
+    ("with ignite", BitFlags::EMPTY, BitFlags::EMPTY, flags!(ModFlag::Ignite), &[]),
+    ("for ignite", BitFlags::EMPTY, BitFlags::EMPTY, flags!(ModFlag::Ignite), &[]),
     ("if you have at least 6 life masteries allocated", BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY, &[
         Condition::GreaterEqualMasteryAllocated(("Life Mastery", 6)),
     ]),
@@ -149,10 +162,17 @@ const STATS: &[(&'static str, StatId, BitFlags<GemTag>, BitFlags<ItemClass>, Bit
     ("cooldown recovery rate", StatId::CooldownRecoverySpeed, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("projectile speed", StatId::ProjectileSpeed, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("trap throwing speed", StatId::TrapThrowingSpeed, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("mine throwing speed", StatId::MineThrowingSpeed, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("totem placement speed", StatId::TotemPlacementSpeed, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("maximum number of summoned totems", StatId::MaximumTotems, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("maximum chance to block attack damage", StatId::MaximumChanceToBlockAttackDamage, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("maximum chance to block spell damage", StatId::MaximumChanceToBlockSpellDamage, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("chance to block attack damage", StatId::ChanceToBlockAttackDamage, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("chance to block spell damage", StatId::ChanceToBlockSpellDamage, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("chance to block", StatId::ChanceToBlockAttackDamage, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY), // local on shields
     ("chance to suppress spell damage", StatId::ChanceToSuppressSpellDamage, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("chance to evade attack hits", StatId::ChanceToEvade, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("chance to avoid all damage from hits", StatId::ChanceToAvoidDamageFromHits, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("chance to deal double damage", StatId::ChanceToDealDoubleDamage, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("fire damage over time multiplier", StatId::FireDotMultiplier, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("cold damage over time multiplier", StatId::ColdDotMultiplier, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
@@ -163,11 +183,16 @@ const STATS: &[(&'static str, StatId, BitFlags<GemTag>, BitFlags<ItemClass>, Bit
     ("lightning damage penetration", StatId::LightningDamagePen, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("cold damage penetration", StatId::ColdDamagePen, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("chaos damage penetration", StatId::ChaosDamagePen, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("fire exposure", StatId::FireExposure, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("cold exposure", StatId::ColdExposure, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("lightning exposure", StatId::LightningExposure, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("damage taken from damage over time", StatId::DamageOverTimeTaken, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("fire damage over time", StatId::FireDamageOverTime, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("cold damage over time", StatId::ColdDamageOverTime, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("chaos damage over time", StatId::ChaosDamageOverTime, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("physical damage over time", StatId::PhysicalDamageOverTime, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("damage over time", StatId::DamageOverTime, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("burning damage", StatId::FireDamageOverTime, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("physical damage reduction", StatId::PhysicalDamageReduction, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("fire damage", StatId::FireDamage, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("cold damage", StatId::ColdDamage, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
@@ -184,9 +209,39 @@ const STATS: &[(&'static str, StatId, BitFlags<GemTag>, BitFlags<ItemClass>, Bit
     ("area of effect", StatId::AreaOfEffect, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("accuracy rating", StatId::AccuracyRating, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("movement speed", StatId::MovementSpeed, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("poison duration", StatId::PoisonDuration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("ignite duration on enemies", StatId::IgniteDuration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("ignite duration", StatId::IgniteDuration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("shock duration on enemies", StatId::ShockDuration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("shock duration", StatId::ShockDuration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("chill duration on enemies", StatId::ChillDuration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("chill duration", StatId::ChillDuration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("freeze duration on enemies", StatId::FreezeDuration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("freeze duration", StatId::FreezeDuration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("skill effect duration", StatId::SkillEffectDuration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("duration", StatId::Duration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("impale effect", StatId::ImpaleEffect, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("physical damage leeched as life", StatId::PhysicalLifeLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("fire damage leeched as life", StatId::FireLifeLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("cold damage leeched as life", StatId::ColdLifeLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("lightning damage leeched as life", StatId::LightningLifeLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("chaos damage leeched as life", StatId::ChaosLifeLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("damage leeched as life", StatId::LifeLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("physical damage leeched as mana", StatId::PhysicalManaLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("fire damage leeched as mana", StatId::FireManaLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("cold damage leeched as mana", StatId::ColdManaLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("lightning damage leeched as mana", StatId::LightningManaLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("chaos damage leeched as mana", StatId::ChaosManaLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("damage leeched as mana", StatId::ManaLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("physical damage leeched as energy shield", StatId::PhysicalEnergyShieldLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("fire damage leeched as energy shield", StatId::FireEnergyShieldLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("cold damage leeched as energy shield", StatId::ColdEnergyShieldLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("lightning damage leeched as energy shield", StatId::LightningEnergyShieldLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("chaos damage leeched as energy shield", StatId::ChaosEnergyShieldLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("damage leeched as energy shield", StatId::EnergyShieldLeech, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("maximum total life recovery per second from leech", StatId::MaximumLifeLeechRate, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("maximum total mana recovery per second from leech", StatId::MaximumManaLeechRate, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("maximum total energy shield recovery per second from leech", StatId::MaximumEnergyShieldLeechRate, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("minimum frenzy charges", StatId::MinimumFrenzyCharges, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("minimum power charges", StatId::MinimumPowerCharges, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("minimum endurance charges", StatId::MinimumEnduranceCharges, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
@@ -199,11 +254,18 @@ const STATS: &[(&'static str, StatId, BitFlags<GemTag>, BitFlags<ItemClass>, Bit
     ("minimum rage", StatId::MinimumRage, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("maximum rage", StatId::MaximumRage, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("maximum energy shield", StatId::MaximumEnergyShield, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("life recovery rate", StatId::LifeRecoveryRate, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("mana recovery rate", StatId::ManaRecoveryRate, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("energy shield recovery rate", StatId::EnergyShieldRecoveryRate, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("start of energy shield recharge", StatId::EnergyShieldRechargeStart, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("energy shield recharge rate", StatId::EnergyShieldRechargeRate, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("energy shield", StatId::MaximumEnergyShield, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("restoration of ward", StatId::WardRestoration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("ward", StatId::Ward, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("life regeneration rate", StatId::LifeRegenerationRate, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("mana regeneration rate", StatId::ManaRegenerationRate, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("mana reservation efficiency", StatId::ManaReservationEfficiency, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("life reservation efficiency", StatId::LifeReservationEfficiency, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("critical strike chance", StatId::CriticalStrikeChance, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("critical strike multiplier", StatId::CriticalStrikeMultiplier, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("armour", StatId::Armour, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
@@ -222,6 +284,8 @@ const STATS: &[(&'static str, StatId, BitFlags<GemTag>, BitFlags<ItemClass>, Bit
     ("flask effect duration", StatId::FlaskEffectDuration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("flask recovery rate", StatId::FlaskRecoveryRate, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("flask charges used", StatId::FlaskChargesUsed, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("life recovery from flasks", StatId::FlaskRecoveryAmount, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("mana recovery from flasks", StatId::FlaskRecoveryAmount, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("mana cost", StatId::ManaCost, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("life cost", StatId::LifeCost, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("cost", StatId::Cost, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
@@ -230,8 +294,23 @@ const STATS: &[(&'static str, StatId, BitFlags<GemTag>, BitFlags<ItemClass>, Bit
     ("chance to cause bleeding", StatId::ChanceToBleed, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("chance to ignite", StatId::ChanceToIgnite, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("chance to shock", StatId::ChanceToShock, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("chance to freeze", StatId::ChanceToFreeze, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("duration of ignite on you", StatId::IgniteDurationOnSelf, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("duration of poisons on you", StatId::PoisonDurationOnSelf, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("effect of shock", StatId::ShockEffect, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("effect of chill", StatId::ChillEffect, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("physical damage over time taken", StatId::PhysicalDamageOverTimeTaken, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("damage over time taken", StatId::DamageOverTimeTaken, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("reflected damage taken", StatId::ReflectedDamageTaken, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("physical damage taken", StatId::PhysicalDamageTaken, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("fire damage taken", StatId::FireDamageTaken, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("cold damage taken", StatId::ColdDamageTaken, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("lightning damage taken", StatId::LightningDamageTaken, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("chaos damage taken", StatId::ChaosDamageTaken, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("damage taken", StatId::DamageTaken, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("chance to impale enemies on hit", StatId::ChanceToImpale, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("chance to poison on hit", StatId::ChanceToPoison, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
-    ("poison duration", StatId::PoisonDuration, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
+    ("effect of your curses", StatId::CurseEffect, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("effect of non-curse auras from your skills", StatId::AuraEffect, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("life", StatId::MaximumLife, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
     ("mana", StatId::MaximumMana, BitFlags::EMPTY, BitFlags::EMPTY, BitFlags::EMPTY),
@@ -239,6 +318,16 @@ const STATS: &[(&'static str, StatId, BitFlags<GemTag>, BitFlags<ItemClass>, Bit
 
 lazy_static! {
     static ref CORES: Vec<(Regex, Box<dyn Fn(&Captures) -> Option<Vec<Mod>> + Send + Sync>)> = vec![
+        (
+            regex!(r"^flasks applied to you have ([0-9]+)% (increased|reduced) effect$"),
+            Box::new(|c| {
+                let mut amount = i64::from_str(&c[1]).unwrap();
+                if &c[2] == "reduced" {
+                    amount = amount.neg();
+                }
+                Some(vec![Mod { stat: StatId::FlaskEffect, typ: Type::Inc, amount, ..Default::default() }])
+            })
+        ),
         (
             regex!(r"^([0-9]+)% (increased|reduced) ([a-z -]+)$"),
             Box::new(|c| {
@@ -253,6 +342,18 @@ lazy_static! {
                     Mod { stat: s.0, typ: Type::Inc, amount, tags: s.1, weapons: s.2, flags: s.3, ..Default::default() }
                 }).collect())
             })
+        ), (
+            regex!(r"^([0-9]+)% (faster|slower) ([a-z -]+)$"),
+            Box::new(|c| {
+                let stat_tags = parse_stat(&c[3])?;
+                let mut amount = i64::from_str(&c[1]).unwrap();
+                if &c[2] == "slower" {
+                    amount = amount.neg();
+                }
+                Some(stat_tags.iter().map(|s| {
+                    Mod { stat: s.0, typ: Type::Inc, amount, tags: s.1, weapons: s.2, flags: s.3, ..Default::default() }
+                }).collect())
+            })
         ), (
             regex!(r"^([+-]?[0-9]+)%? (?:additional )?(?:to )?(?:all )?([a-z -]+)$"),
             Box::new(|c| {
@@ -328,24 +429,70 @@ lazy_static! {
                 ])
             })
         ), (
-            regex!(r"^regenerate ([0-9.]+)(% of)? (life|mana) per second$"),
+            regex!(r"^regenerate ([0-9.]+)(% of)? (life|mana|energy shield) per second$"),
             Box::new(|c| {
                 let stat = match(&c[3], c.get(2).is_some()) {
                     ("life", false) => StatId::LifeRegeneration,
                     ("life", true) => StatId::LifeRegenerationPct,
                     ("mana", false) => StatId::ManaRegeneration,
                     ("mana", true) => StatId::ManaRegenerationPct,
+                    ("energy shield", false) => StatId::EnergyShieldRegeneration,
+                    ("energy shield", true) => StatId::EnergyShieldRegenerationPct,
                     _ => panic!(),
                 };
 
                 Some(vec![Mod { stat, typ: Type::Base, amount: parse_val100(&c[1])?, ..Default::default() }])
             })
+        ), (
+            regex!(r"^([0-9.]+)% of (physical |fire |cold |lightning |chaos |elemental )?(attack |spell )?damage leeched as (life|mana|energy shield|life and mana)$"),
+            Box::new(|c| {
+                let amount = parse_val100(&c[1])?;
+                let tags = match c.get(3).map(|t| t.as_str()) {
+                    Some("attack ") => GemTag::Attack.into(),
+                    Some("spell ") => GemTag::Spell.into(),
+                    _ => BitFlags::EMPTY,
+                };
+                let damage_types: &[&str] = match c.get(2).map(|t| t.as_str()) {
+                    Some("elemental ") => &["fire ", "cold ", "lightning "],
+                    Some(dt) => &[dt][..],
+                    None => &[""],
+                };
+                let resources: &[&str] = match &c[4] {
+                    "life and mana" => &["life", "mana"],
+                    _ => &[&c[4]][..],
+                };
+                let mut mods = vec![];
+                for resource in resources {
+                    for dt in damage_types {
+                        let stat_tags = STATS_MAP.get(format!("{}damage leeched as {}", dt, resource).as_str())?;
+                        mods.push(Mod { stat: stat_tags.0, typ: Type::Base, amount, tags, ..Default::default() });
+                    }
+                }
+                Some(mods)
+            })
+        ), (
+            regex!(r"^([0-9]+)% of damage is taken from mana before life$"),
+            Box::new(|c| {
+                Some(vec![Mod { stat: StatId::DamageTakenFromManaBeforeLife, typ: Type::Base, amount: i64::from_str(&c[1]).unwrap(), ..Default::default() }])
+            })
+        ), (
+            regex!(r"^prevent \+([0-9]+)% of suppressed spell damage$"),
+            Box::new(|c| {
+                Some(vec![Mod { stat: StatId::SuppressedSpellDamagePrevented, typ: Type::Base, amount: i64::from_str(&c[1]).unwrap(), ..Default::default() }])
+            })
         ), (
             regex!(r"^damage penetrates ([0-9]+)% ([a-z]+) resistance$"),
             Box::new(|c| {
                 let stat_tags_1 = STATS_MAP.get(format!("{} damage penetration", &c[2]).as_str()).cloned()?;
                 Some(vec![Mod { stat: stat_tags_1.0, typ: Type::Base, amount: parse_val100(&c[1])?, tags: stat_tags_1.1, ..Default::default() }])
             })
+        ), (
+            regex!(r"^inflict ([a-z]+) exposure on hit(, applying -([0-9]+)% to [a-z]+ resistance)?$"),
+            Box::new(|c| {
+                let stat_tags_1 = STATS_MAP.get(format!("{} exposure", &c[1]).as_str()).cloned()?;
+                let amount = c.get(3).map_or(EXPOSURE_DEFAULT, |amount| i64::from_str(amount.as_str()).unwrap());
+                Some(vec![Mod { stat: stat_tags_1.0, typ: Type::Base, amount, tags: stat_tags_1.1, ..Default::default() }])
+            })
         ), (
             regex!(r"^your ([a-z -]+) is equal to ([0-9]+)% of your ([a-z -]+)$"),
             Box::new(|c| {
@@ -413,6 +560,13 @@ lazy_static! {
                 Some(vec![Mod { stat: *stat, typ: Type::Base, amount: i64::from_str(&c[1]).unwrap(), ..Default::default() }])
             })
         ),
+        (
+            regex!(r"^gain ([0-9]+)% of (physical|cold|fire|lightning) damage as extra (fire|cold|lightning|chaos) damage$"),
+            Box::new(|c| {
+                let stat = GAINS.get(&(c[2].to_string(), c[3].to_string()))?;
+                Some(vec![Mod { stat: *stat, typ: Type::Base, amount: i64::from_str(&c[1]).unwrap(), ..Default::default() }])
+            })
+        ),
     ];
 
     // amounts can be modified by parsing code
@@ -448,6 +602,9 @@ lazy_static! {
         map.insert("strength's damage bonus applies to all spell damage as well", vec![
             Mod { stat: StatId::Damage, typ: Type::Inc, amount: 1, tags: GemTag::Spell.into(), mutations: stackvec!(Mutation::MultiplierStat((5, StatId::Strength))), ..Default::default()},
         ]);
+        map.insert("you can apply an additional curse", vec![
+            Mod { stat: StatId::CurseLimit, typ: Type::Base, amount: 1, ..Default::default()},
+        ]);
         map.insert("removes all energy shield", vec![
             Mod { stat: StatId::MaximumEnergyShield, typ: Type::Override, amount: 0, ..Default::default()},
         ]);
@@ -457,12 +614,16 @@ lazy_static! {
     static ref MULTISTATS: FxHashMap<&'static str, Vec<StatId>> = {
         let mut map = FxHashMap::default();
         map.insert("attributes", vec![StatId::Strength, StatId::Dexterity, StatId::Intelligence]);
+        map.insert("reservation efficiency", vec![StatId::ManaReservationEfficiency, StatId::LifeReservationEfficiency]);
         map.insert("maximum elemental resistances", vec![StatId::MaximumFireResistance, StatId::MaximumColdResistance, StatId::MaximumLightningResistance]);
         map.insert("elemental resistances", vec![StatId::FireResistance, StatId::ColdResistance, StatId::LightningResistance]);
         map.insert("maximum resistances", vec![StatId::MaximumFireResistance, StatId::MaximumColdResistance, StatId::MaximumLightningResistance, StatId::MaximumChaosResistance]);
         map.insert("resistances", vec![StatId::FireResistance, StatId::ColdResistance, StatId::LightningResistance, StatId::ChaosResistance]);
         map.insert("elemental damage", vec![StatId::FireDamage, StatId::ColdDamage, StatId::LightningDamage]);
+        map.insert("elemental damage taken", vec![StatId::FireDamageTaken, StatId::ColdDamageTaken, StatId::LightningDamageTaken]);
         map.insert("attack and cast speed", vec![StatId::AttackSpeed, StatId::CastSpeed]);
+        map.insert("recovery rate of life and energy shield", vec![StatId::LifeRecoveryRate, StatId::EnergyShieldRecoveryRate]);
+        map.insert("recovery rate of life, mana and energy shield", vec![StatId::LifeRecoveryRate, StatId::ManaRecoveryRate, StatId::EnergyShieldRecoveryRate]);
         map.insert("armour and evasion", vec![StatId::Armour, StatId::EvasionRating]);
         map
     };
@@ -490,6 +651,21 @@ lazy_static! {
         map.insert(("fire".into(), "chaos".into()), StatId::FireToChaosConversion);
         map
     };
+
+    static ref GAINS: FxHashMap<(String, String), StatId> = {
+        let mut map = FxHashMap::default();
+        map.insert(("physical".into(), "lightning".into()), StatId::PhysicalGainedAsLightning);
+        map.insert(("physical".into(), "cold".into()), StatId::PhysicalGainedAsCold);
+        map.insert(("physical".into(), "fire".into()), StatId::PhysicalGainedAsFire);
+        map.insert(("physical".into(), "chaos".into()), StatId::PhysicalGainedAsChaos);
+        map.insert(("lightning".into(), "cold".into()), StatId::LightningGainedAsCold);
+        map.insert(("lightning".into(), "fire".into()), StatId::LightningGainedAsFire);
+        map.insert(("lightning".into(), "chaos".into()), StatId::LightningGainedAsChaos);
+        map.insert(("cold".into(), "fire".into()), StatId::ColdGainedAsFire);
+        map.insert(("cold".into(), "chaos".into()), StatId::ColdGainedAsChaos);
+        map.insert(("fire".into(), "chaos".into()), StatId::FireGainedAsChaos);
+        map
+    };
 }
 
 pub fn lol() {
@@ -542,9 +718,13 @@ pub enum Condition {
     SlotsHaveDefence((Defence, &'static [Slot])),
     SlotLesserEqualStats((Slot, i64, &'static [StatId])),
     GreaterEqualMasteryAllocated((&'static str, u32)),
+    /// Derived from the leech of the skill, see `calc::is_leeching`
+    Leeching,
+    /// At least one equipped flask is active
+    UsingFlask,
 }
 
-#[derive(Default, Debug, Clone, Copy)]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
 pub enum Source {
     #[default]
     Innate,
@@ -563,10 +743,15 @@ pub enum ModFlag {
     Ailment,
     Bleed,
     Poison,
+    Ignite,
     Aura,
     Buff,
+    Curse,
 }
 
+/// Resistance lowered by exposure when the mod inflicting it doesn't say
+const EXPOSURE_DEFAULT: i64 = 10;
+
 const MUTATIONS_COUNT: usize = 2;
 const CONDITIONS_COUNT: usize = 2;
 
@@ -771,6 +956,41 @@ pub fn parse_mod(input: &str, source: Source) -> Option<Vec<Mod>> {
     None
 }
 
+/// Stats only found on flasks, elsewhere the same words mean something else
+const FLASK_STATS: &[(&str, StatId)] = &[
+    ("life recovered", StatId::FlaskRecoveryAmount),
+    ("mana recovered", StatId::FlaskRecoveryAmount),
+    ("amount recovered", StatId::FlaskRecoveryAmount),
+    ("recovery rate", StatId::FlaskRecoveryRate),
+    ("maximum charges", StatId::FlaskMaximumCharges),
+    ("charge recovery", StatId::FlaskChargesGained),
+    ("charges per use", StatId::FlaskChargesUsed),
+    ("effect", StatId::FlaskEffect),
+];
+
+lazy_static! {
+    static ref FLASK_CORE: Regex = regex!(r"^\+?([0-9]+)(?:%)? (increased|reduced|to) ([a-z ]+)$");
+}
+
+/// Attempts to parse a modifier found on a flask, like "+10 to maximum charges".
+/// Falls back to `parse_mod` for everything not specific to flasks.
+pub fn parse_flask_mod(input: &str, source: Source) -> Option<Vec<Mod>> {
+    let lowercase = input.to_lowercase();
+    if let Some(cap) = FLASK_CORE.captures(&lowercase) &&
+       let Some((_, stat_id)) = FLASK_STATS.iter().find(|(name, _)| *name == &cap[3])
+    {
+        let amount = i64::from_str(&cap[1]).unwrap();
+        let (typ, amount) = match &cap[2] {
+            "to" => (Type::Base, amount),
+            "reduced" => (Type::Inc, amount.neg()),
+            _ => (Type::Inc, amount),
+        };
+        return Some(vec![Mod { stat: *stat_id, typ, amount, source, ..Default::default() }]);
+    }
+
+    parse_mod(input, source)
+}
+
 #[test]
 fn test_parse() {
     assert!(parse_mod("50% increased damage", Source::Innate).is_some());
@@ -787,6 +1007,54 @@ fn test_parse() {
     assert!(parse_mod("40% of physical damage converted to fire damage", Source::Innate).is_some());
     assert!(parse_mod("50% of lightning damage converted to cold damage", Source::Innate).is_some());
     assert!(parse_mod("100% of fire damage converted to chaos damage", Source::Innate).is_some());
+    assert!(parse_mod("gain 30% of physical damage as extra chaos damage", Source::Innate).is_some());
+    assert!(parse_mod("gain 10% of cold damage as extra fire damage", Source::Innate).is_some_and(|mods| mods[0].stat == StatId::ColdGainedAsFire));
+    assert!(parse_mod("25% increased burning damage", Source::Innate).is_some());
+    assert!(parse_mod("15% increased ignite duration on enemies", Source::Innate).is_some());
+    assert!(parse_mod("+12% to damage over time multiplier for ignite", Source::Innate).is_some());
+    assert!(parse_mod("20% increased poison duration", Source::Innate).is_some());
+    assert!(parse_mod("+10% to damage over time multiplier for poison", Source::Innate).is_some());
+    assert!(parse_mod("30% increased effect of shock", Source::Innate).is_some());
+    assert!(parse_mod("20% increased freeze duration on enemies", Source::Innate).is_some());
+    assert!(parse_mod("10% chance to freeze", Source::Innate).is_some());
+    assert!(parse_mod("25% chance to impale enemies on hit with attacks", Source::Innate).is_some());
+    assert!(parse_mod("damage penetrates 10% cold resistance", Source::Innate).is_some());
+    assert!(parse_mod("0.3% of physical attack damage leeched as life", Source::Innate).is_some());
+    assert!(parse_mod("0.2% of attack damage leeched as mana", Source::Innate).is_some());
+    assert!(parse_mod("1% of elemental damage leeched as energy shield", Source::Innate).is_some());
+    assert!(parse_mod("0.4% of physical attack damage leeched as life and mana", Source::Innate).is_some());
+    assert!(parse_mod("20% increased maximum total life recovery per second from leech", Source::Innate).is_some());
+    assert!(parse_mod("40% of damage is taken from mana before life", Source::Innate).is_some());
+    assert!(parse_mod("prevent +3% of suppressed spell damage", Source::Innate).is_some());
+    assert!(parse_mod("+2% to maximum chance to block attack damage", Source::Innate).is_some());
+    assert!(parse_mod("+5% chance to evade attack hits", Source::Innate).is_some());
+    assert!(parse_mod("regenerate 1.5% of energy shield per second", Source::Innate).is_some());
+    assert!(parse_mod("30% faster start of energy shield recharge", Source::Innate).is_some());
+    assert!(parse_mod("20% faster restoration of ward", Source::Innate).is_some());
+    assert!(parse_mod("+50 to ward", Source::Innate).is_some());
+    assert!(parse_mod("10% increased life recovery rate", Source::Innate).is_some());
+    assert!(parse_mod("60% less recovery rate of life and energy shield", Source::Innate).is_some());
+    assert!(parse_mod("+10 to maximum charges", Source::Innate).is_none());
+    assert!(parse_mod("25% increased effect", Source::Innate).is_none());
+    assert!(parse_flask_mod("+10 to maximum charges", Source::Innate).is_some());
+    assert!(parse_flask_mod("25% increased charge recovery", Source::Innate).is_some());
+    assert!(parse_flask_mod("25% increased effect", Source::Innate).is_some_and(|mods| mods[0].stat == StatId::FlaskEffect));
+    assert!(parse_flask_mod("+10% to fire resistance", Source::Innate).is_some_and(|mods| mods[0].stat == StatId::FireResistance));
+    assert!(parse_mod("40% increased armour during effect", Source::Innate).is_some());
+    assert!(parse_mod("10% increased damage during any flask effect", Source::Innate).is_some());
+    assert!(parse_mod("10% increased cast speed while you have arcane surge", Source::Innate).is_some());
+    assert!(parse_mod("flasks applied to you have 10% increased effect", Source::Innate).is_some());
+    assert!(parse_mod("inflict fire exposure on hit", Source::Innate).is_some_and(|mods| mods[0].amount == 10));
+    assert!(parse_mod("inflict cold exposure on hit, applying -12% to cold resistance", Source::Innate).is_some_and(|mods| mods[0].stat == StatId::ColdExposure && mods[0].amount == 12));
+    assert!(parse_mod("10% increased effect of your curses", Source::Innate).is_some());
+    assert!(parse_mod("you can apply an additional curse", Source::Innate).is_some_and(|mods| mods[0].stat == StatId::CurseLimit));
+    assert!(parse_mod("+1 to maximum number of summoned totems", Source::Innate).is_some());
+    assert!(parse_mod("10% reduced fire damage taken", Source::Innate).is_some_and(|mods| mods[0].stat == StatId::FireDamageTaken));
+    assert!(parse_mod("5% reduced elemental damage taken", Source::Innate).is_some_and(|mods| mods.len() == 3));
+    assert!(parse_mod("10% reduced damage taken from damage over time", Source::Innate).is_some_and(|mods| mods[0].stat == StatId::DamageOverTimeTaken));
+    assert!(parse_mod("50% reduced reflected damage taken", Source::Innate).is_some_and(|mods| mods[0].stat == StatId::ReflectedDamageTaken));
+    assert!(parse_mod("60% less duration of ignite on you", Source::Innate).is_some_and(|mods| mods[0].stat == StatId::IgniteDurationOnSelf));
+    assert!(parse_mod("20% increased totem placement speed", Source::Innate).is_some());
     // Invalid conversion direction (chaos can't convert to physical)
     assert!(parse_mod("40% of chaos damage converted to physical damage", Source::Innate).is_none());
 }
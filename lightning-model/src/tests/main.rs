@@ -12,7 +12,7 @@ fn fetch() -> Result<Build, Box<dyn std::error::Error>> {
         }
     }
 
-    let player = import::character("Steelmage", "SteelMyTink")?;
+    let (player, _) = import::character("Steelmage", "SteelMyTink")?;
     serde_json::to_writer(&fs::File::create(BUILD_PATH)?, &player)?;
     Ok(player)
 }
@@ -58,7 +58,7 @@ fn main() {
         }
     }*/
 
-    calc_defence(&player);
+    calc_defence(&player, false);
 
     //dbg!(&data::GEMS["Fireball"]);
 }
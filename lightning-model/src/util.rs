@@ -1,4 +1,5 @@
 use crate::build::Build;
+use crate::diagnostics::Diagnostics;
 #[cfg(feature="import")]
 use crate::import;
 use serde::{Deserialize, Deserializer};
@@ -13,9 +14,9 @@ pub fn load_build(path: &PathBuf) -> Result<Build, Box<dyn std::error::Error>> {
 }
 
 #[cfg(feature="import")]
-pub fn fetch_build(account: &str, character: &str) -> Result<Build, Box<dyn std::error::Error>> {
-    let player = import::character(account, character)?;
-    Ok(player)
+pub fn fetch_build(account: &str, character: &str) -> Result<(Build, Diagnostics), Box<dyn std::error::Error>> {
+    let (player, diagnostics) = import::character(account, character)?;
+    Ok((player, diagnostics))
 }
 
 pub fn deserialize_null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>